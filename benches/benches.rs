@@ -2,9 +2,8 @@
 
 extern crate criterion;
 
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{Duration, TimeZone, Utc};
 use criterion::{criterion_group, criterion_main, Criterion};
-use financial;
 
 fn bench_npv_100_value(c: &mut Criterion) {
     c.bench_function("bench_npv_100_value", |b| {
@@ -20,6 +19,35 @@ fn bench_npv_10000_value(c: &mut Criterion) {
     });
 }
 
+// npv as it was before discount factors were accumulated on the fly, allocating every power up front,
+// kept as a baseline for the npv benchmarks
+fn npv_with_powers(rate: f64, values: &[f64]) -> f64 {
+    let base = 1. + rate;
+    let powers = (1..values.len()).fold(vec![base], |mut pows, _| {
+        pows.push(*pows.last().unwrap() * base);
+        pows
+    });
+
+    powers
+        .iter()
+        .zip(values.iter())
+        .fold(0., |sum, (p, v)| sum + v / p)
+}
+
+fn bench_npv_100_value_powers(c: &mut Criterion) {
+    c.bench_function("bench_npv_100_value_powers", |b| {
+        let x: [f64; 100] = [100.; 100];
+        b.iter(|| npv_with_powers(0.1, &x));
+    });
+}
+
+fn bench_npv_10000_value_powers(c: &mut Criterion) {
+    c.bench_function("bench_npv_10000_value_powers", |b| {
+        let x: [f64; 10000] = [100.; 10000];
+        b.iter(|| npv_with_powers(0.1, &x));
+    });
+}
+
 fn bench_npv_10000_value_zero_rate(c: &mut Criterion) {
     c.bench_function("bench_npv_10000_value_zero_rate", |b| {
         let x: [f64; 10000] = [100.; 10000];
@@ -34,6 +62,51 @@ fn bench_irr(c: &mut Criterion) {
     });
 }
 
+// a 30 year monthly cashflow with a guess far from its IRR, where Newton's method needs a bracketed fallback
+fn long_cashflow() -> Vec<f64> {
+    let mut cf = vec![2000.; 361];
+    cf[0] = -100000.;
+    cf
+}
+
+fn solver_options(method: financial::SolverMethod) -> financial::SolverOptions {
+    financial::SolverOptions {
+        method,
+        ..Default::default()
+    }
+}
+
+fn bench_irr_newton_bisection(c: &mut Criterion) {
+    let cf = long_cashflow();
+    let options = solver_options(financial::SolverMethod::NewtonBisection);
+    c.bench_function("bench_irr_newton_bisection", |b| {
+        b.iter(|| financial::irr_with_options(&cf, Some(-0.5), &options));
+    });
+}
+
+fn bench_irr_hybrid(c: &mut Criterion) {
+    let cf = long_cashflow();
+    let options = solver_options(financial::SolverMethod::Hybrid);
+    c.bench_function("bench_irr_hybrid", |b| {
+        b.iter(|| financial::irr_with_options(&cf, Some(-0.5), &options));
+    });
+}
+
+fn bench_irr_360_value(c: &mut Criterion) {
+    let cf = long_cashflow();
+    c.bench_function("bench_irr_360_value", |b| {
+        b.iter(|| financial::irr(&cf, None));
+    });
+}
+
+fn bench_irr_10000_value(c: &mut Criterion) {
+    let mut cf = vec![100.; 10000];
+    cf[0] = -500000.;
+    c.bench_function("bench_irr_10000_value", |b| {
+        b.iter(|| financial::irr(&cf, None));
+    });
+}
+
 fn bench_mirr(c: &mut Criterion) {
     let cf = [-500., 100., 100., 100., 100., 100.];
     c.bench_function("bench_mirr", |b| {
@@ -41,15 +114,72 @@ fn bench_mirr(c: &mut Criterion) {
     });
 }
 
+fn bench_mirr_360_value(c: &mut Criterion) {
+    let cf = long_cashflow();
+    c.bench_function("bench_mirr_360_value", |b| {
+        b.iter(|| financial::mirr(&cf, 0.05, 0.1));
+    });
+}
+
+fn bench_mirr_10000_value(c: &mut Criterion) {
+    let cf: Vec<f64> = (0..10000)
+        .map(|i| if i % 7 == 0 { -1000. } else { 300. })
+        .collect();
+    c.bench_function("bench_mirr_10000_value", |b| {
+        b.iter(|| financial::mirr(&cf, 0.05, 0.1));
+    });
+}
+
+// mirr as it was before discount factors were accumulated on the fly, kept as a baseline for the mirr benchmarks
+fn mirr_with_powers(values: &[f64], finance_rate: f64, reinvest_rate: f64) -> f64 {
+    let powers = |base: f64| {
+        (0..values.len()).fold(vec![1.], |mut pows, _| {
+            pows.push(*pows.last().unwrap() * base);
+            pows
+        })
+    };
+
+    let negative_cf_pv = powers(1. + finance_rate)
+        .iter()
+        .zip(values.iter())
+        .filter(|(_r, &v)| v < 0.)
+        .fold(0., |sum, (r, v)| sum + v / r);
+
+    let positive_cf_fv = powers(1. + reinvest_rate)
+        .iter()
+        .zip(values.iter().rev())
+        .filter(|(_r, &v)| v > 0.)
+        .fold(0., |sum, (r, v)| sum + v * r);
+
+    let periods = values.len() as f64 - 1.;
+    (positive_cf_fv / -negative_cf_pv).powf(1. / periods) - 1.
+}
+
+fn bench_mirr_360_value_powers(c: &mut Criterion) {
+    let cf = long_cashflow();
+    c.bench_function("bench_mirr_360_value_powers", |b| {
+        b.iter(|| mirr_with_powers(&cf, 0.05, 0.1));
+    });
+}
+
+fn bench_mirr_10000_value_powers(c: &mut Criterion) {
+    let cf: Vec<f64> = (0..10000)
+        .map(|i| if i % 7 == 0 { -1000. } else { 300. })
+        .collect();
+    c.bench_function("bench_mirr_10000_value_powers", |b| {
+        b.iter(|| mirr_with_powers(&cf, 0.05, 0.1));
+    });
+}
+
 fn bench_xnpv(c: &mut Criterion) {
     let cf = [-500., 100., 100., 100., 100., 100.];
     let dates = [
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2016, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2017, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2018, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2019, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2020, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 7, 8).and_hms(0, 0, 0), Utc),
+        Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
     ];
     c.bench_function("bench_xnpv", |b| {
         b.iter(|| financial::xnpv(0.1, &cf, &dates));
@@ -59,27 +189,61 @@ fn bench_xnpv(c: &mut Criterion) {
 fn bench_xirr(c: &mut Criterion) {
     let cf = [-500., 100., 100., 100., 100., 100.];
     let dates = [
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2016, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2017, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2018, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2019, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2020, 7, 8).and_hms(0, 0, 0), Utc),
-        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 7, 8).and_hms(0, 0, 0), Utc),
+        Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
     ];
     c.bench_function("bench_xirr", |b| {
         b.iter(|| financial::xirr(&cf, &dates, None));
     });
 }
 
+fn bench_xirr_newton_bisection(c: &mut Criterion) {
+    let cf = long_cashflow();
+    let dates: Vec<_> = (0..cf.len() as i64)
+        .map(|i| Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap() + Duration::days(30 * i))
+        .collect();
+    let options = solver_options(financial::SolverMethod::NewtonBisection);
+    c.bench_function("bench_xirr_newton_bisection", |b| {
+        b.iter(|| financial::xirr_with_options(&cf, &dates, None, &options));
+    });
+}
+
+fn bench_xirr_hybrid(c: &mut Criterion) {
+    let cf = long_cashflow();
+    let dates: Vec<_> = (0..cf.len() as i64)
+        .map(|i| Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap() + Duration::days(30 * i))
+        .collect();
+    let options = solver_options(financial::SolverMethod::Hybrid);
+    c.bench_function("bench_xirr_hybrid", |b| {
+        b.iter(|| financial::xirr_with_options(&cf, &dates, None, &options));
+    });
+}
+
 criterion_group!(
     benches,
     bench_npv_100_value,
     bench_npv_10000_value,
+    bench_npv_100_value_powers,
+    bench_npv_10000_value_powers,
     bench_npv_10000_value_zero_rate,
     bench_irr,
+    bench_irr_newton_bisection,
+    bench_irr_hybrid,
+    bench_irr_360_value,
+    bench_irr_10000_value,
     bench_mirr,
+    bench_mirr_360_value,
+    bench_mirr_10000_value,
+    bench_mirr_360_value_powers,
+    bench_mirr_10000_value_powers,
     bench_xnpv,
-    bench_xirr
+    bench_xirr,
+    bench_xirr_newton_bisection,
+    bench_xirr_hybrid
 );
 
 criterion_main!(benches);
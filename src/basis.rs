@@ -0,0 +1,74 @@
+use crate::day_count::DayCount;
+use crate::error::FinancialError;
+use std::convert::TryFrom;
+
+/// The day count bases of Excel's `YEARFRAC` and bond functions, numbered like Excel's `basis` argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Basis {
+    /// 0, US (NASD) 30/360.
+    #[default]
+    UsNasd30360 = 0,
+    /// 1, actual days over the actual days of the years.
+    ActualActual = 1,
+    /// 2, actual days over 360.
+    Actual360 = 2,
+    /// 3, actual days over 365.
+    Actual365 = 3,
+    /// 4, European 30/360.
+    European30360 = 4,
+}
+
+impl TryFrom<i32> for Basis {
+    type Error = FinancialError;
+
+    /// Converts Excel's `basis` argument, failing outside of 0 to 4 like Excel's `#NUM!`.
+    fn try_from(basis: i32) -> Result<Basis, FinancialError> {
+        match basis {
+            0 => Ok(Basis::UsNasd30360),
+            1 => Ok(Basis::ActualActual),
+            2 => Ok(Basis::Actual360),
+            3 => Ok(Basis::Actual365),
+            4 => Ok(Basis::European30360),
+            _ => Err(FinancialError::InvalidArgument {
+                name: "basis",
+                reason: "must be between 0 and 4",
+            }),
+        }
+    }
+}
+
+/// Maps a basis to the closest day count convention.
+///
+/// Excel's `YEARFRAC` departs from these conventions for bases 0 and 1, see `financial::naive_date::yearfrac()`.
+impl From<Basis> for DayCount {
+    fn from(basis: Basis) -> DayCount {
+        match basis {
+            Basis::UsNasd30360 => DayCount::Thirty360Us,
+            Basis::ActualActual => DayCount::ActActIsda,
+            Basis::Actual360 => DayCount::Act360,
+            Basis::Actual365 => DayCount::Act365Fixed,
+            Basis::European30360 => DayCount::ThirtyE360,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basis_converts_from_excel_numbers() {
+        for n in 0..=4 {
+            assert_eq!(Basis::try_from(n).unwrap() as i32, n);
+        }
+        assert!(Basis::try_from(-1).is_err());
+        assert!(Basis::try_from(5).is_err());
+        assert_eq!(Basis::default(), Basis::UsNasd30360);
+    }
+
+    #[test]
+    fn basis_maps_to_day_count() {
+        assert_eq!(DayCount::from(Basis::Actual360), DayCount::Act360);
+        assert_eq!(DayCount::from(Basis::European30360), DayCount::ThirtyE360);
+    }
+}
@@ -0,0 +1,467 @@
+use crate::error::FinancialError;
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Tells business days apart from weekends and holidays.
+pub trait Calendar: Debug + Send + Sync {
+    fn is_business_day(&self, date: NaiveDate) -> bool;
+
+    /// Returns the number of business days from `start` included to `end` excluded, negative when `end` is before `start`.
+    fn business_days_between(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        if end < start {
+            return -self.business_days_between(end, start);
+        }
+
+        start
+            .iter_days()
+            .take_while(|date| *date < end)
+            .filter(|date| self.is_business_day(*date))
+            .count() as i64
+    }
+
+    /// Moves `date` to a business day according to `convention`.
+    ///
+    /// `date` is returned as is if no business day can be reached before the end of the range of `NaiveDate`.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use financial::{BusinessDayConvention, Calendar, HolidayCalendar};
+    ///
+    /// let calendar = HolidayCalendar::default();
+    /// let saturday = NaiveDate::from_ymd_opt(2021, 7, 31).unwrap();
+    /// assert_eq!(
+    ///     calendar.adjust(saturday, BusinessDayConvention::Following),
+    ///     NaiveDate::from_ymd_opt(2021, 8, 2).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     calendar.adjust(saturday, BusinessDayConvention::ModifiedFollowing),
+    ///     NaiveDate::from_ymd_opt(2021, 7, 30).unwrap()
+    /// );
+    /// ```
+    fn adjust(&self, date: NaiveDate, convention: BusinessDayConvention) -> NaiveDate {
+        let following = || roll(self, date, NaiveDate::succ_opt);
+        let preceding = || roll(self, date, NaiveDate::pred_opt);
+
+        match convention {
+            BusinessDayConvention::Unadjusted => date,
+            BusinessDayConvention::Following => following(),
+            BusinessDayConvention::Preceding => preceding(),
+            BusinessDayConvention::ModifiedFollowing => {
+                let adjusted = following();
+                if adjusted.month() == date.month() {
+                    adjusted
+                } else {
+                    preceding()
+                }
+            }
+            BusinessDayConvention::ModifiedPreceding => {
+                let adjusted = preceding();
+                if adjusted.month() == date.month() {
+                    adjusted
+                } else {
+                    following()
+                }
+            }
+        }
+    }
+
+    /// Moves every date of a schedule to a business day according to `convention`, see `adjust`.
+    ///
+    /// The adjusted dates can be fed to `financial::naive_date::xnpv()` and `financial::naive_date::xirr()`.
+    fn adjust_schedule(
+        &self,
+        dates: &[NaiveDate],
+        convention: BusinessDayConvention,
+    ) -> Vec<NaiveDate> {
+        dates
+            .iter()
+            .map(|date| self.adjust(*date, convention))
+            .collect()
+    }
+}
+
+// Steps from `date` until a business day, giving up at the end of the range of dates.
+fn roll<C: Calendar + ?Sized>(
+    calendar: &C,
+    date: NaiveDate,
+    step: fn(&NaiveDate) -> Option<NaiveDate>,
+) -> NaiveDate {
+    let mut adjusted = date;
+    while !calendar.is_business_day(adjusted) {
+        match step(&adjusted) {
+            Some(next) => adjusted = next,
+            None => return date,
+        }
+    }
+
+    adjusted
+}
+
+/// How a date that isn't a business day is moved to one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusinessDayConvention {
+    /// The date is kept as is.
+    Unadjusted,
+    /// The first business day after the date.
+    Following,
+    /// The first business day after the date, unless it's in the next month, then the last business day before it.
+    ModifiedFollowing,
+    /// The last business day before the date.
+    Preceding,
+    /// The last business day before the date, unless it's in the previous month, then the first business day after it.
+    ModifiedPreceding,
+}
+
+/// The days of the week that aren't business days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weekend {
+    // one bit per weekday, from Monday
+    days: u8,
+}
+
+impl Weekend {
+    /// Returns a weekend made of `days`, which must leave at least one business day in the week.
+    pub fn new(days: &[Weekday]) -> Result<Weekend, FinancialError> {
+        let days = days
+            .iter()
+            .fold(0, |mask, day| mask | 1 << day.num_days_from_monday());
+
+        if days == 0b111_1111 {
+            return Err(FinancialError::InvalidArgument {
+                name: "weekend",
+                reason: "must leave at least one business day",
+            });
+        }
+
+        Ok(Weekend { days })
+    }
+
+    pub fn contains(&self, day: Weekday) -> bool {
+        self.days & 1 << day.num_days_from_monday() != 0
+    }
+}
+
+impl TryFrom<u32> for Weekend {
+    type Error = FinancialError;
+
+    /// Converts the weekend numbers of Excel's `WORKDAY.INTL` and `NETWORKDAYS.INTL`: 1 to 7 for two days
+    /// weekends from Saturday and Sunday to Friday and Saturday, 11 to 17 for a single day from Sunday to Saturday.
+    fn try_from(number: u32) -> Result<Weekend, FinancialError> {
+        let days = match number {
+            1..=7 => [
+                Weekday::Sat,
+                Weekday::Sun,
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            11..=17 => [
+                Weekday::Sun,
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Sat,
+            ],
+            _ => {
+                return Err(FinancialError::InvalidArgument {
+                    name: "weekend",
+                    reason: "must be between 1 and 7 or between 11 and 17",
+                })
+            }
+        };
+
+        if number <= 7 {
+            let first = number as usize - 1;
+            Weekend::new(&[days[first], days[(first + 1) % 7]])
+        } else {
+            Weekend::new(&[days[number as usize - 11]])
+        }
+    }
+}
+
+impl FromStr for Weekend {
+    type Err = FinancialError;
+
+    /// Parses the weekend strings of Excel's `WORKDAY.INTL` and `NETWORKDAYS.INTL`, seven `0` or `1` from Monday
+    /// to Sunday where `1` is a day of the weekend.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::Weekday;
+    /// use financial::Weekend;
+    ///
+    /// let weekend: Weekend = "0000110".parse().unwrap();
+    /// assert!(weekend.contains(Weekday::Fri) && weekend.contains(Weekday::Sat));
+    /// assert!(!weekend.contains(Weekday::Sun));
+    /// ```
+    fn from_str(s: &str) -> Result<Weekend, FinancialError> {
+        let invalid = FinancialError::InvalidArgument {
+            name: "weekend",
+            reason: "must be seven 0 or 1 from Monday to Sunday",
+        };
+        if s.len() != 7 {
+            return Err(invalid);
+        }
+
+        let mut days = vec![];
+        for (day, flag) in s.chars().enumerate() {
+            match flag {
+                '0' => {}
+                '1' => days.push(Weekday::try_from(day as u8).unwrap()),
+                _ => return Err(invalid),
+            }
+        }
+
+        Weekend::new(&days)
+    }
+}
+
+/// Saturday and Sunday.
+impl Default for Weekend {
+    fn default() -> Weekend {
+        Weekend::new(&[Weekday::Sat, Weekday::Sun]).unwrap()
+    }
+}
+
+/// A calendar where the days of a weekend, Saturday and Sunday by default, and a list of holidays aren't business days.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HolidayCalendar {
+    holidays: Vec<NaiveDate>,
+    weekend: Weekend,
+}
+
+impl HolidayCalendar {
+    pub fn new(holidays: &[NaiveDate]) -> HolidayCalendar {
+        let mut holidays = holidays.to_vec();
+        holidays.sort();
+        holidays.dedup();
+
+        HolidayCalendar {
+            holidays,
+            weekend: Weekend::default(),
+        }
+    }
+
+    /// Reads the holidays from a file, see `from_str` for its format.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<HolidayCalendar> {
+        std::fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Replaces the Saturday and Sunday weekend.
+    pub fn with_weekend(self, weekend: Weekend) -> HolidayCalendar {
+        HolidayCalendar { weekend, ..self }
+    }
+
+    pub fn holidays(&self) -> &[NaiveDate] {
+        &self.holidays
+    }
+
+    pub fn weekend(&self) -> Weekend {
+        self.weekend
+    }
+}
+
+impl FromStr for HolidayCalendar {
+    type Err = FinancialError;
+
+    /// Parses one `YYYY-MM-DD` holiday per line. Blank lines and everything after a `#` are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use financial::HolidayCalendar;
+    ///
+    /// let calendar: HolidayCalendar = "# US\n2021-07-05 # Independence Day\n\n2021-12-24\n".parse().unwrap();
+    /// assert_eq!(calendar.holidays().len(), 2);
+    /// ```
+    fn from_str(s: &str) -> Result<HolidayCalendar, FinancialError> {
+        let holidays = s
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                NaiveDate::parse_from_str(line, "%Y-%m-%d").map_err(|_| {
+                    FinancialError::InvalidArgument {
+                        name: "holidays",
+                        reason: "must be dates formatted as YYYY-MM-DD, one per line",
+                    }
+                })
+            })
+            .collect::<Result<Vec<NaiveDate>, FinancialError>>()?;
+
+        Ok(HolidayCalendar::new(&holidays))
+    }
+}
+
+impl Calendar for HolidayCalendar {
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.weekend.contains(date.weekday()) && self.holidays.binary_search(&date).is_err()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn holiday_calendar_skips_weekends_and_holidays() {
+        let calendar = HolidayCalendar::new(&[date(2021, 12, 24), date(2021, 12, 24)]);
+
+        assert_eq!(calendar.holidays().len(), 1);
+        assert!(calendar.is_business_day(date(2021, 12, 23)));
+        assert!(!calendar.is_business_day(date(2021, 12, 24)));
+        assert!(!calendar.is_business_day(date(2021, 12, 25)));
+        assert!(!calendar.is_business_day(date(2021, 12, 26)));
+    }
+
+    #[test]
+    fn holiday_calendar_honours_weekend() {
+        let weekend = Weekend::new(&[Weekday::Fri, Weekday::Sat]).unwrap();
+        let calendar = HolidayCalendar::default().with_weekend(weekend);
+
+        assert_eq!(calendar.weekend(), weekend);
+        assert!(!calendar.is_business_day(date(2021, 12, 24)));
+        assert!(!calendar.is_business_day(date(2021, 12, 25)));
+        assert!(calendar.is_business_day(date(2021, 12, 26)));
+
+        assert!(Weekend::new(&[]).is_ok());
+        assert!(Weekend::new(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn weekend_converts_from_excel() {
+        let weekend = |number| Weekend::try_from(number).unwrap();
+        let days = |days: &[Weekday]| Weekend::new(days).unwrap();
+
+        assert_eq!(weekend(1), Weekend::default());
+        assert_eq!(weekend(2), days(&[Weekday::Sun, Weekday::Mon]));
+        assert_eq!(weekend(7), days(&[Weekday::Fri, Weekday::Sat]));
+        assert_eq!(weekend(11), days(&[Weekday::Sun]));
+        assert_eq!(weekend(17), days(&[Weekday::Sat]));
+        assert!(Weekend::try_from(0).is_err());
+        assert!(Weekend::try_from(8).is_err());
+        assert!(Weekend::try_from(18).is_err());
+
+        assert_eq!("0000011".parse::<Weekend>().unwrap(), Weekend::default());
+        assert_eq!("0000000".parse::<Weekend>().unwrap(), days(&[]));
+        assert_eq!("1000001".parse::<Weekend>().unwrap(), weekend(2));
+        assert!("1111111".parse::<Weekend>().is_err());
+        assert!("000011".parse::<Weekend>().is_err());
+        assert!("000001a".parse::<Weekend>().is_err());
+    }
+
+    #[test]
+    fn business_days_between_works() {
+        let calendar = HolidayCalendar::new(&[date(2021, 12, 24)]);
+        let start = date(2021, 12, 20);
+        let end = date(2021, 12, 27);
+
+        assert_eq!(calendar.business_days_between(start, end), 4);
+        assert_eq!(calendar.business_days_between(end, start), -4);
+        assert_eq!(calendar.business_days_between(start, start), 0);
+    }
+
+    #[test]
+    fn adjust_follows_conventions() {
+        let calendar = HolidayCalendar::new(&[date(2021, 5, 31)]);
+        let adjust = |date, convention| calendar.adjust(date, convention);
+
+        // Saturday, May 29th, before a holiday at the end of the month
+        let saturday = date(2021, 5, 29);
+        assert_eq!(
+            adjust(saturday, BusinessDayConvention::Unadjusted),
+            saturday
+        );
+        assert_eq!(
+            adjust(saturday, BusinessDayConvention::Following),
+            date(2021, 6, 1)
+        );
+        assert_eq!(
+            adjust(saturday, BusinessDayConvention::ModifiedFollowing),
+            date(2021, 5, 28)
+        );
+        assert_eq!(
+            adjust(saturday, BusinessDayConvention::Preceding),
+            date(2021, 5, 28)
+        );
+
+        // Sunday, August 1st
+        let sunday = date(2021, 8, 1);
+        assert_eq!(
+            adjust(sunday, BusinessDayConvention::Preceding),
+            date(2021, 7, 30)
+        );
+        assert_eq!(
+            adjust(sunday, BusinessDayConvention::ModifiedPreceding),
+            date(2021, 8, 2)
+        );
+
+        let business_day = date(2021, 6, 1);
+        assert_eq!(
+            adjust(business_day, BusinessDayConvention::Preceding),
+            business_day
+        );
+        assert_eq!(
+            adjust(NaiveDate::MAX, BusinessDayConvention::Following),
+            NaiveDate::MAX
+        );
+    }
+
+    #[test]
+    fn adjust_schedule_works() {
+        let calendar = HolidayCalendar::default();
+        let dates = [date(2021, 1, 1), date(2021, 4, 3), date(2021, 7, 3)];
+
+        assert_eq!(
+            calendar.adjust_schedule(&dates, BusinessDayConvention::ModifiedFollowing),
+            vec![date(2021, 1, 1), date(2021, 4, 5), date(2021, 7, 5)]
+        );
+    }
+
+    #[test]
+    fn holiday_calendar_parses_and_loads_holidays() {
+        let text = "# holidays\n2021-12-31\n\n2021-12-24 # Christmas Eve\n";
+        let calendar: HolidayCalendar = text.parse().unwrap();
+        assert_eq!(
+            calendar.holidays(),
+            &[date(2021, 12, 24), date(2021, 12, 31)]
+        );
+
+        assert!("2021-13-01".parse::<HolidayCalendar>().is_err());
+        assert!("24/12/2021".parse::<HolidayCalendar>().is_err());
+
+        // unique to this process and test, so concurrent test runs don't share the file
+        let path = std::env::temp_dir().join(format!(
+            "financial_{}_holiday_calendar_parses_and_loads_holidays.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, text).unwrap();
+        assert_eq!(HolidayCalendar::load(&path).unwrap(), calendar);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(HolidayCalendar::load(&path).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod find_root;
+pub mod root_isolation;
 pub mod utils;
 
 pub const PRECISION: f64 = 1e-7;
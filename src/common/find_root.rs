@@ -1,81 +1,243 @@
-use crate::common::PRECISION;
+use crate::common::{utils, PRECISION};
+use crate::error::FinancialError;
+use crate::solver::{SolvePhase, SolveReport, SolverMethod, SolverOptions};
+use num_traits::Float;
 
-const NEWTON_MAX_ITERATION: u32 = 20;
-const BISECTION_MAX_ITERATION: u32 = 2000;
 const INITIAL_GUESS: f64 = 0.;
 
-pub fn find_root<F>(x: Option<f64>, func: F, bounds_search_expansion_factor: f64) -> Option<f64>
+struct Attempt {
+    root: Option<f64>,
+    iterations: u32,
+    last_estimate: f64,
+    iterates: Vec<f64>,
+}
+
+impl Attempt {
+    fn new(root: Option<f64>, iterations: u32, last_estimate: f64, iterates: Vec<f64>) -> Attempt {
+        Attempt {
+            root,
+            iterations,
+            last_estimate,
+            iterates,
+        }
+    }
+}
+
+pub fn find_root<F>(x: Option<f64>, func: F, options: &SolverOptions) -> Result<f64, FinancialError>
 where
     F: Fn(f64) -> f64,
 {
+    find_root_with_derivative(x, &func, |x| finite_difference(&func, x), options)
+}
+
+/// Finds a root of `func` close to the guess `x`, for any floating point type.
+///
+/// The solver keeps its estimates in `f64`, like the rates and tolerances of `options`, and evaluates `func` in `T`.
+/// With `f32`, `options.f_tolerance` must be loosened to what `func` can resolve.
+///
+/// # Example
+/// ```
+/// let root = financial::generic::find_root(
+///     Some(1_f32),
+///     |x| x * x - 2.,
+///     &financial::SolverOptions::default(),
+/// );
+/// assert!((root.unwrap() - std::f32::consts::SQRT_2).abs() < 1e-6);
+/// ```
+pub fn find_root_generic<T, F>(
+    x: Option<T>,
+    func: F,
+    options: &SolverOptions,
+) -> Result<T, FinancialError>
+where
+    T: Float,
+    F: Fn(T) -> T,
+{
+    let func = |x: f64| utils::cast::<f64, T>(func(utils::cast(x)));
+
+    find_root(x.map(utils::cast), func, options).map(utils::cast)
+}
+
+/// Same as `find_root`, with the derivative of `func` provided for Newton's method.
+///
+/// `SolverMethod::NewtonBisection` ignores `derivative` and estimates it by finite differences instead.
+pub fn find_root_with_derivative<F, D>(
+    x: Option<f64>,
+    func: F,
+    derivative: D,
+    options: &SolverOptions,
+) -> Result<f64, FinancialError>
+where
+    F: Fn(f64) -> f64,
+    D: Fn(f64) -> f64,
+{
+    solve(x, func, derivative, options, false)?.into_result()
+}
+
+/// Same as `find_root_with_derivative`, reporting how the solver went instead of only the root.
+///
+/// Only invalid options or guess are returned as an error, a solver that didn't converge is reported.
+/// Every estimate is recorded in `SolveReport::iterates` when `trace` is set.
+pub fn solve<F, D>(
+    x: Option<f64>,
+    func: F,
+    derivative: D,
+    options: &SolverOptions,
+    trace: bool,
+) -> Result<SolveReport, FinancialError>
+where
+    F: Fn(f64) -> f64,
+    D: Fn(f64) -> f64,
+{
+    options.validate()?;
+
     let x = match x {
+        Some(num) if !num.is_finite() => {
+            return Err(FinancialError::InvalidArgument {
+                name: "guess",
+                reason: "must be finite",
+            })
+        }
         Some(num) => num,
         None => INITIAL_GUESS,
     };
+    let x = x.max(options.min_rate).min(options.max_rate);
     let f = |x| func(x);
-    let newton_val = newton(x, f);
+    let newton_attempt = match options.method {
+        SolverMethod::Hybrid => newton(x, f, derivative, options, trace),
+        SolverMethod::NewtonBisection => newton(x, f, |x| finite_difference(f, x), options, trace),
+        SolverMethod::Brent => Attempt::new(None, 0, x, vec![]),
+    };
     let same_sign = |x: f64, y: f64| {
         x.is_sign_positive() && y.is_sign_positive() || x.is_sign_negative() && y.is_sign_negative()
     };
+    let in_range = |x: f64| options.min_rate <= x && x <= options.max_rate;
+    let report = |phase, root, last_estimate: f64| SolveReport {
+        root,
+        phase,
+        newton_iterations: newton_attempt.iterations,
+        bracket_search_iterations: 0,
+        bracketed_iterations: 0,
+        last_estimate,
+        residual: f(last_estimate),
+        bracket: None,
+        iterates: newton_attempt.iterates.clone(),
+    };
 
-    if newton_val.is_some() && same_sign(newton_val.unwrap(), x) {
-        newton_val
-    } else if let Some(b_pos) = find_bounds(x, Bounds::new_positive(), f, bounds_search_expansion_factor) {
-        bisection(b_pos, f)
-    } else if let Some(b_neg) = find_bounds(x, Bounds::new_negative(), f, bounds_search_expansion_factor) {
-        bisection(b_neg, f)
-    } else {
-        None
+    match newton_attempt.root {
+        Some(root) if same_sign(root, x) && in_range(root) => {
+            return Ok(report(SolvePhase::Newton, Some(root), root))
+        }
+        _ => {}
     }
+
+    let mut bracket_search_iterations = 0;
+    let mut bracket = |bounds: Option<Bounds>| {
+        bounds.and_then(|b| {
+            let (bounds, iterations) = find_bounds(x, b, f, options);
+            bracket_search_iterations += iterations;
+            bounds
+        })
+    };
+    let bounds = match bracket(Bounds::new_positive(options)) {
+        Some(b_pos) => Some(b_pos),
+        None => bracket(Bounds::new_negative(options)),
+    };
+
+    let mut report = match bounds {
+        None => report(
+            SolvePhase::BracketSearch,
+            None,
+            newton_attempt.last_estimate,
+        ),
+        Some(bounds) => {
+            let range = (bounds.lower, bounds.upper);
+            let (phase, attempt) = match options.method {
+                SolverMethod::NewtonBisection => {
+                    (SolvePhase::Bisection, bisection(bounds, f, options, trace))
+                }
+                SolverMethod::Hybrid | SolverMethod::Brent => {
+                    (SolvePhase::Brent, brent(bounds, f, options, trace))
+                }
+            };
+
+            let mut report = report(phase, attempt.root, attempt.last_estimate);
+            report.bracketed_iterations = attempt.iterations;
+            report.bracket = Some(range);
+            report.iterates.extend(attempt.iterates);
+            report
+        }
+    };
+    report.bracket_search_iterations = bracket_search_iterations;
+
+    Ok(report)
+}
+
+fn finite_difference<F>(f: F, x: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    (f(x + PRECISION) - f(x - PRECISION)) / (2. * PRECISION)
 }
 
-fn newton<F>(x: f64, f: F) -> Option<f64>
+fn newton<F, D>(x: f64, f: F, df: D, options: &SolverOptions, trace: bool) -> Attempt
 where
     F: Fn(f64) -> f64,
+    D: Fn(f64) -> f64,
 {
     let mut x = x;
-    let df = |x: f64| (f(x + PRECISION) - f(x - PRECISION)) / (2. * PRECISION);
+    let mut iterates = vec![];
 
-    for _ in 1..NEWTON_MAX_ITERATION {
+    for iteration in 1..=options.newton_max_iterations {
         let fx = f(x);
         let dfx = df(x);
 
         let new_x = x - fx / dfx;
+        if trace {
+            iterates.push(new_x);
+        }
 
-        if (new_x - x).abs() <= PRECISION || fx.abs() <= PRECISION {
-            return Some(new_x);
+        if (new_x - x).abs() <= options.x_tolerance || fx.abs() <= options.f_tolerance {
+            return Attempt::new(Some(new_x), iteration, new_x, iterates);
         }
 
         x = new_x;
     }
 
-    None
+    Attempt::new(None, options.newton_max_iterations, x, iterates)
 }
 
-fn bisection<F>(bounds: Bounds, f: F) -> Option<f64>
+fn bisection<F>(bounds: Bounds, f: F, options: &SolverOptions, trace: bool) -> Attempt
 where
     F: Fn(f64) -> f64,
 {
     let mut a = bounds.lower;
     let mut b = bounds.upper;
-    for _ in 1..BISECTION_MAX_ITERATION {
+    let mut iterates = vec![];
+
+    let tolerance = options.f_tolerance;
+
+    for iteration in 1..=options.bracketed_max_iterations {
         let fa = f(a);
-        if fa.abs() < PRECISION {
-            return Some(a);
+        if fa.abs() < tolerance {
+            return Attempt::new(Some(a), iteration, a, iterates);
         } else {
             let fb = f(b);
-            if fb.abs() < PRECISION {
-                return Some(b);
+            if fb.abs() < tolerance {
+                return Attempt::new(Some(b), iteration, b, iterates);
             } else {
                 if fa * fb > 0. {
-                    return None;
+                    return Attempt::new(None, iteration, a, iterates);
                 }
 
                 let mid = a + (b - a) / 2.;
                 let fmid = f(mid);
+                if trace {
+                    iterates.push(mid);
+                }
 
-                if fmid.abs() < PRECISION {
-                    return Some(mid);
+                if fmid.abs() < tolerance {
+                    return Attempt::new(Some(mid), iteration, mid, iterates);
                 } else {
                     let fafmid = fa * fmid;
                     if fafmid < 0. {
@@ -83,21 +245,129 @@ where
                     } else if fafmid > 0. {
                         a = mid;
                     } else {
-                        panic!("it should never get here");
+                        // NaN values, the function can't be bisected any further
+                        return Attempt::new(None, iteration, mid, iterates);
                     }
                 }
             }
         }
     }
 
-    None
+    let last_estimate = a + (b - a) / 2.;
+    Attempt::new(
+        None,
+        options.bracketed_max_iterations,
+        last_estimate,
+        iterates,
+    )
+}
+
+// Brent's method: inverse quadratic interpolation or secant steps while they make progress,
+// falling back to bisection otherwise, so it never does worse than bisecting the bracket.
+fn brent<F>(bounds: Bounds, f: F, options: &SolverOptions, trace: bool) -> Attempt
+where
+    F: Fn(f64) -> f64,
+{
+    let tolerance = options.f_tolerance;
+    let (mut a, mut b) = (bounds.lower, bounds.upper);
+    let (mut fa, mut fb) = (f(a), f(b));
+    let mut iterates = vec![];
+
+    if fa.abs() < tolerance {
+        return Attempt::new(Some(a), 0, a, iterates);
+    }
+    if fb.is_nan() || fa * fb > 0. {
+        return Attempt::new(None, 0, a, iterates);
+    }
+
+    // b is the best estimate, c the previous one, with the root between b and c
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+
+    for iteration in 1..=options.bracketed_max_iterations {
+        if fb.abs() < tolerance {
+            return Attempt::new(Some(b), iteration, b, iterates);
+        }
+        if fb.is_nan() {
+            return Attempt::new(None, iteration, b, iterates);
+        }
+
+        if fb.is_sign_positive() == fc.is_sign_positive() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let step_tolerance = 2. * f64::EPSILON * b.abs();
+        let half_width = (c - b) / 2.;
+        if half_width.abs() <= step_tolerance {
+            // the bracket can't be narrowed any further
+            return Attempt::new(None, iteration, b, iterates);
+        }
+
+        if e.abs() >= step_tolerance && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2. * half_width * s, 1. - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2. * half_width * q * (q - r) - (b - a) * (r - 1.)),
+                    (q - 1.) * (r - 1.) * (s - 1.),
+                )
+            };
+            if p > 0. {
+                q = -q;
+            }
+            p = p.abs();
+
+            let interpolation_limit = 3. * half_width * q - (step_tolerance * q).abs();
+            if 2. * p < interpolation_limit.min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = half_width;
+                e = d;
+            }
+        } else {
+            d = half_width;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > step_tolerance {
+            d
+        } else {
+            step_tolerance.copysign(half_width)
+        };
+        fb = f(b);
+        if trace {
+            iterates.push(b);
+        }
+    }
+
+    Attempt::new(None, options.bracketed_max_iterations, b, iterates)
 }
 
-fn find_bounds<F>(x: f64, bounds: Bounds, f: F, expansion_factor: f64) -> Option<Bounds>
+// Returns the bracket found, if any, and the number of brackets tried.
+fn find_bounds<F>(x: f64, bounds: Bounds, f: F, options: &SolverOptions) -> (Option<Bounds>, u32)
 where
     F: Fn(f64) -> f64,
 {
-    let shift = 0.01;
+    let shift = options.initial_bracket;
+    let expansion_factor = options.expansion_factor;
     let adjust_to_min = |val| {
         if val <= bounds.lower {
             bounds.lower + PRECISION
@@ -113,14 +383,16 @@ where
         }
     };
 
+    // a guess outside of the bounds starts the search from the closest bound
+    let x = x.max(bounds.lower).min(bounds.upper);
     let mut low = adjust_to_min(x - shift);
     let mut upp = adjust_to_max(x + shift);
-    for _ in 1..60 {
+    for iteration in 1..=options.bracket_max_iterations {
         let lower = adjust_to_min(low);
         let upper = adjust_to_max(upp);
         let product = f(lower) * f(upper);
         if product <= 0. {
-            return Some(Bounds::new_from_range(lower, upper));
+            return (Bounds::new_from_range(lower, upper), iteration);
         } else {
             low = lower + expansion_factor * (lower - upper);
             upp = upper + expansion_factor * (upper - lower);
@@ -128,7 +400,7 @@ where
         }
     }
 
-    None
+    (None, options.bracket_max_iterations)
 }
 
 #[derive(Debug)]
@@ -138,24 +410,167 @@ struct Bounds {
 }
 
 impl Bounds {
-    fn new_positive() -> Bounds {
-        Bounds {
-            lower: 0.,
-            upper: f64::MAX,
-        }
+    fn new_positive(options: &SolverOptions) -> Option<Bounds> {
+        Bounds::new_from_range(options.min_rate.max(0.), options.max_rate)
+    }
+
+    fn new_negative(options: &SolverOptions) -> Option<Bounds> {
+        Bounds::new_from_range(options.min_rate, options.max_rate.min(0.))
     }
 
-    fn new_negative() -> Bounds {
-        Bounds {
-            lower: f64::MIN,
-            upper: 0.,
+    fn new_from_range(lower: f64, upper: f64) -> Option<Bounds> {
+        if lower <= upper {
+            Some(Bounds { lower, upper })
+        } else {
+            None
         }
     }
+}
 
-    fn new_from_range(lower: f64, upper: f64) -> Bounds {
-        if lower > upper {
-            panic!("lower cannot be greater than upper bounds")
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_root_works() {
+        let root = find_root(Some(0.), |x| x * x - 4., &SolverOptions::default()).unwrap();
+        assert!((root - 2.).abs() <= PRECISION, "got {}", root);
+    }
+
+    #[test]
+    fn find_root_generic_works_with_f32() {
+        let root = find_root_generic(Some(0_f32), |x| x * x - 4., &SolverOptions::default());
+        assert_eq!(root, Ok(2.));
+        assert!(find_root_generic(Some(f32::NAN), |x| x, &SolverOptions::default()).is_err());
+    }
+
+    #[test]
+    fn find_root_fails_without_root() {
+        assert!(matches!(
+            find_root(None, |x| x * x + 1., &SolverOptions::default()),
+            Err(FinancialError::DidNotConverge { .. })
+        ));
+    }
+
+    #[test]
+    fn find_root_fails_on_nan() {
+        assert!(find_root(None, |_| f64::NAN, &SolverOptions::default()).is_err());
+        assert!(find_root(
+            None,
+            |x| if x > 1. { f64::NAN } else { x - 2. },
+            &SolverOptions::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn find_root_rejects_non_finite_guess() {
+        assert!(find_root(Some(f64::NAN), |x| x, &SolverOptions::default()).is_err());
+        assert!(find_root(Some(f64::INFINITY), |x| x, &SolverOptions::default()).is_err());
+    }
+
+    #[test]
+    fn find_bounds_starts_from_closest_bound() {
+        let options = SolverOptions::default();
+        let (bounds, _) = find_bounds(
+            0.1,
+            Bounds::new_negative(&options).unwrap(),
+            |x| x + 0.5,
+            &options,
+        );
+        let bounds = bounds.unwrap();
+        assert!(bounds.lower <= -0.5 && bounds.upper < 0.);
+    }
+
+    #[test]
+    fn find_root_stays_within_rate_range() {
+        let f = |x: f64| (x - 0.5) * (x + 0.5);
+        let options = SolverOptions {
+            max_rate: 0.,
+            ..Default::default()
+        };
+        let root = find_root(Some(0.4), f, &options).unwrap();
+        assert!((root - -0.5).abs() <= PRECISION, "got {}", root);
+
+        let options = SolverOptions {
+            min_rate: -0.4,
+            max_rate: 0.4,
+            ..Default::default()
+        };
+        assert!(find_root(Some(0.4), f, &options).is_err());
+    }
+
+    #[test]
+    fn find_root_honours_iteration_limits() {
+        let options = SolverOptions {
+            newton_max_iterations: 1,
+            bracket_max_iterations: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            find_root(Some(1.), |x| x * x * x - 8., &options),
+            Err(FinancialError::DidNotConverge { iterations: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn find_root_rejects_invalid_options() {
+        let options = SolverOptions {
+            f_tolerance: 0.,
+            ..Default::default()
+        };
+        assert!(find_root(None, |x| x - 1., &options).is_err());
+    }
+
+    #[test]
+    fn find_root_methods_agree() {
+        let f = |x: f64| (x - 3.).atan();
+        for method in [
+            SolverMethod::Hybrid,
+            SolverMethod::Brent,
+            SolverMethod::NewtonBisection,
+        ]
+        .iter()
+        {
+            let options = SolverOptions {
+                method: *method,
+                ..Default::default()
+            };
+            let root = find_root(Some(0.), f, &options).unwrap();
+            assert!((root - 3.).abs() <= PRECISION, "{:?} got {}", method, root);
         }
-        Bounds { lower, upper }
+    }
+
+    #[test]
+    fn brent_needs_fewer_evaluations_than_bisection() {
+        let evaluations = std::cell::Cell::new(0);
+        let f = |x: f64| {
+            evaluations.set(evaluations.get() + 1);
+            x.exp() - 1000.
+        };
+        let bounds = Bounds::new_from_range(0., 100.).unwrap();
+        let options = SolverOptions::default();
+
+        let root = brent(bounds, f, &options, false).root.unwrap();
+        assert!((root - 1000f64.ln()).abs() <= PRECISION, "got {}", root);
+        let brent_evaluations = evaluations.replace(0);
+
+        let bounds = Bounds::new_from_range(0., 100.).unwrap();
+        bisection(bounds, f, &options, false).root.unwrap();
+        assert!(brent_evaluations < evaluations.get());
+    }
+
+    #[test]
+    fn find_root_with_derivative_uses_it() {
+        let calls = std::cell::Cell::new(0);
+        let df = |x: f64| {
+            calls.set(calls.get() + 1);
+            2. * x
+        };
+        let root =
+            find_root_with_derivative(Some(1.), |x| x * x - 4., df, &SolverOptions::default())
+                .unwrap();
+        assert!((root - 2.).abs() <= PRECISION, "got {}", root);
+        assert!(calls.get() > 0);
     }
 }
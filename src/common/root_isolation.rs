@@ -0,0 +1,183 @@
+// Real root isolation for generalized polynomials, sums of `coefficient * x^exponent` terms with real exponents,
+// restricted to positive x. Both NPV and XNPV are such polynomials in the discount factor x = 1 / (1 + rate).
+//
+// Between two consecutive roots of the derivative a polynomial is monotonic, so it has at most one root there.
+// Dividing by the lowest power of x first doesn't move the positive roots, and makes the derivative drop a term,
+// so recursing on derivatives terminates after as many levels as there are terms.
+
+/// Merges terms with the same exponent, drops zero coefficients and sorts them by exponent.
+pub fn normalize_terms(terms: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut sorted = terms.to_vec();
+    sorted.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+    let mut merged: Vec<(f64, f64)> = Vec::with_capacity(sorted.len());
+    for (coefficient, exponent) in sorted {
+        match merged.last_mut() {
+            Some(last) if last.1 == exponent => last.0 += coefficient,
+            _ => merged.push((coefficient, exponent)),
+        }
+    }
+    merged.retain(|term| term.0 != 0.);
+
+    merged
+}
+
+/// Number of sign changes of the coefficients, an upper bound of the number of positive roots (Descartes' rule of signs).
+pub fn sign_changes(terms: &[(f64, f64)]) -> usize {
+    terms
+        .windows(2)
+        .filter(|pair| pair[0].0.is_sign_negative() != pair[1].0.is_sign_negative())
+        .count()
+}
+
+/// No positive root is larger than this bound.
+pub fn root_bound(terms: &[(f64, f64)]) -> f64 {
+    let n = terms.len();
+    if n < 2 {
+        return 1.;
+    }
+
+    // for x >= 1, the leading term outweighs all the others once x^gap > sum(|others|) / |leading|
+    let (leading_coefficient, leading_exponent) = terms[n - 1];
+    let gap = leading_exponent - terms[n - 2].1;
+    let others: f64 = terms[..n - 1].iter().map(|term| term.0.abs()).sum();
+    let bound = (others / leading_coefficient.abs()).powf(1. / gap).max(1.);
+
+    if bound.is_finite() {
+        bound
+    } else {
+        f64::MAX
+    }
+}
+
+/// Returns the roots within `[lower, upper]`, in increasing order, where `0 <= lower <= upper` and `terms` are normalized.
+pub fn positive_roots(terms: &[(f64, f64)], lower: f64, upper: f64) -> Vec<f64> {
+    // a single term has no positive root
+    if terms.len() < 2 || lower > upper {
+        return vec![];
+    }
+
+    let lowest_exponent = terms[0].1;
+    let shifted: Vec<(f64, f64)> = terms
+        .iter()
+        .map(|(coefficient, exponent)| (*coefficient, exponent - lowest_exponent))
+        .collect();
+    let g = |x: f64| evaluate(&shifted, x);
+
+    // x * g'(x), scaled so that repeated derivatives don't overflow
+    let scale = shifted[1..]
+        .iter()
+        .map(|(coefficient, exponent)| (coefficient * exponent).abs())
+        .fold(0., f64::max);
+    let derivative: Vec<(f64, f64)> = shifted[1..]
+        .iter()
+        .map(|(coefficient, exponent)| (coefficient * exponent / scale, *exponent))
+        .collect();
+
+    let mut points = vec![lower];
+    points.extend(positive_roots(&derivative, lower, upper));
+    points.push(upper);
+
+    let mut roots = vec![];
+    if g(lower) == 0. {
+        roots.push(lower);
+    }
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (ga, gb) = (g(a), g(b));
+
+        if gb == 0. {
+            roots.push(b);
+        } else if ga * gb < 0. {
+            roots.push(bisect(g, a, b));
+        }
+    }
+    roots.dedup();
+
+    roots
+}
+
+// Returns a value with the sign of the polynomial at x, and zero at its roots.
+// Above 1 the terms are divided by the highest power of x, so that they don't overflow.
+fn evaluate(terms: &[(f64, f64)], x: f64) -> f64 {
+    let highest_exponent = match terms.last() {
+        Some(term) if x > 1. => term.1,
+        _ => 0.,
+    };
+
+    terms
+        .iter()
+        .map(|(coefficient, exponent)| coefficient * x.powf(exponent - highest_exponent))
+        .sum()
+}
+
+// the function is monotonic on [a, b] and changes sign, so the bracket is narrowed down
+// until it can't be split any further
+fn bisect<G>(g: G, a: f64, b: f64) -> f64
+where
+    G: Fn(f64) -> f64,
+{
+    let (mut a, mut b) = (a, b);
+    let a_is_negative = g(a) < 0.;
+
+    loop {
+        let mid = a + (b - a) / 2.;
+        if mid <= a || mid >= b {
+            return mid;
+        }
+
+        let gmid = g(mid);
+        if gmid == 0. {
+            return mid;
+        } else if (gmid < 0.) == a_is_negative {
+            a = mid;
+        } else {
+            b = mid;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positive_roots_of_polynomial() {
+        // (x - 1)(x - 2)(x - 3)
+        let terms = normalize_terms(&[(-6., 0.), (11., 1.), (-6., 2.), (1., 3.)]);
+        let roots = positive_roots(&terms, 0., root_bound(&terms));
+
+        assert_eq!(roots.len(), 3, "got {:?}", roots);
+        for (root, expected) in roots.iter().zip([1., 2., 3.].iter()) {
+            assert!((root - expected).abs() <= 1e-12, "got {:?}", roots);
+        }
+        assert_eq!(sign_changes(&terms), 3);
+    }
+
+    #[test]
+    fn positive_roots_with_real_exponents() {
+        // x^0.5 - 2 has a single root at 4
+        let terms = normalize_terms(&[(1., 0.5), (-2., 0.)]);
+        assert_eq!(terms, vec![(-2., 0.), (1., 0.5)]);
+        assert_eq!(positive_roots(&terms, 0., root_bound(&terms)), vec![4.]);
+        assert!(positive_roots(&terms, 0., 3.).is_empty());
+    }
+
+    #[test]
+    fn positive_roots_of_high_degree_polynomial() {
+        // 2000 (x + x^2 + ... + x^360) - 100000, whose powers overflow past the root bound
+        let mut terms: Vec<(f64, f64)> = (0..=360).map(|n| (2000., n as f64)).collect();
+        terms[0].0 = -100000.;
+        let roots = positive_roots(&terms, 0., root_bound(&terms));
+
+        assert_eq!(roots.len(), 1, "got {:?}", roots);
+        assert!(evaluate(&terms, roots[0]).abs() <= 1e-7);
+    }
+
+    #[test]
+    fn normalize_terms_merges_exponents() {
+        let terms = normalize_terms(&[(1., 1.), (-1., 0.), (1., 0.), (2., 1.)]);
+        assert_eq!(terms, vec![(3., 1.)]);
+        assert!(positive_roots(&terms, 0., 10.).is_empty());
+    }
+}
@@ -1,47 +1,81 @@
-pub fn powers(base: f64, n: usize, start_from_zero: bool) -> Vec<f64> {
-    let start = if start_from_zero { 0 } else { 1 };
-    let p0 = if start_from_zero { 1. } else { base };
-
-    (start..n).fold(vec![p0], |mut pows, _| {
-        pows.push(pows.last().unwrap() * (base));
-        pows
-    })
-}
+use crate::error::FinancialError;
+use num_traits::Float;
 
-pub fn trim_zeros(values: &[f64]) -> &[f64] {
-    let not_zero = |x: &f64| *x != 0.;
+// converts between float types, values that can't be represented become NaN
+#[inline]
+pub fn cast<T: Float, U: Float>(value: U) -> T {
+    T::from(value).unwrap_or_else(T::nan)
+}
 
-    let begin = values.iter().position(not_zero).unwrap_or(0);
+pub fn trim_zeros<T: Float>(values: &[T]) -> &[T] {
+    let not_zero = |x: &T| !x.is_zero();
 
-    let end = values.iter().rposition(not_zero).unwrap_or(values.len() - 1) + 1;
-    &values[begin..end]
+    match (
+        values.iter().position(not_zero),
+        values.iter().rposition(not_zero),
+    ) {
+        (Some(begin), Some(end)) => &values[begin..=end],
+        _ => &values[0..0],
+    }
 }
 
-pub fn trim_end_zeros(values: &[f64]) -> &[f64] {
-    let not_zero = |x: &f64| *x != 0.;
+pub fn trim_end_zeros<T: Float>(values: &[T]) -> &[T] {
+    let not_zero = |x: &T| !x.is_zero();
 
-    let end = values.iter().rposition(not_zero).unwrap_or(values.len() - 1) + 1;
+    let end = values.iter().rposition(not_zero).map_or(0, |end| end + 1);
 
     &values[0..end]
 }
 
-use chrono::{offset::TimeZone, DateTime};
+pub fn round_to_decimals(value: f64, decimals: Option<u32>) -> f64 {
+    match decimals {
+        Some(d) => {
+            let factor = f64::powi(10., d as i32);
+            (value * factor).round() / factor
+        }
+        None => value,
+    }
+}
+
+use chrono::{offset::TimeZone, DateTime, Datelike, NaiveDate};
 
 #[inline]
 pub fn days_to<T: TimeZone>(d0: DateTime<T>, d1: DateTime<T>) -> f64 {
     d1.signed_duration_since(d0).num_days() as f64
 }
 
+// clamps the day to the end of the resulting month, e.g. Jan 31st + 1 month is Feb 28th
+pub fn add_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    let total_months = (date.year() * 12 + date.month0() as i32).checked_add(months)?;
+    let (year, month) = (
+        total_months.div_euclid(12),
+        total_months.rem_euclid(12) as u32 + 1,
+    );
+
+    (1..=date.day())
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+}
+
 // FIXME: needs to move
-pub fn validate_cashflow_values(values: &[f64]) -> Result<(), &'static str> {
-    let len = values.len();
-    let zeros = values.iter().filter(|x| **x == 0.).count();
-    let negatives = values.iter().filter(|x| x.is_sign_negative()).count();
-
-    if len < 2 || zeros + negatives == len {
-        return Err(
-            "cashflow must contain more than one value, and include positive and negative values",
-        );
+pub fn validate_cashflow_values<T: Float>(values: &[T]) -> Result<(), FinancialError> {
+    let positives = values.iter().filter(|x| **x > T::zero()).count();
+    let negatives = values.iter().filter(|x| **x < T::zero()).count();
+
+    if values.iter().any(|x| !x.is_finite()) {
+        return Err(FinancialError::InvalidArgument {
+            name: "values",
+            reason: "must be finite",
+        });
+    }
+    if values.len() < 2 {
+        return Err(FinancialError::InvalidArgument {
+            name: "values",
+            reason: "must contain more than one value",
+        });
+    }
+    if positives == 0 || negatives == 0 {
+        return Err(FinancialError::NoSignChange);
     }
 
     Ok(())
@@ -0,0 +1,316 @@
+use crate::calendar::Calendar;
+use crate::frequency::Frequency;
+use chrono::{Datelike, NaiveDate};
+use std::sync::Arc;
+
+/// Day count conventions, used to measure the fraction of a year between two dates.
+#[derive(Debug, Clone)]
+pub enum DayCount {
+    /// Actual days over 365, also known as ACT/365F.
+    Act365Fixed,
+    /// Actual days over 360, common for money market instruments.
+    Act360,
+    /// 30/360 bond basis, every month counts as 30 days.
+    Thirty360,
+    /// Actual days in each calendar year over the days of that year.
+    ActActIsda,
+    /// Actual days over the days of the coupon periods at the given frequency, rolled from the start date.
+    ActActIcma(Frequency),
+    /// 30/360 US, the bond basis with the end of February counting as the 30th.
+    Thirty360Us,
+    /// 30E/360, also known as the Eurobond basis, where the 31st always counts as the 30th.
+    ThirtyE360,
+    /// 30E/360 ISDA, where the end of every month counts as the 30th.
+    /// The exception for a termination date at the end of February isn't applied.
+    ThirtyE360Isda,
+    /// Actual days over 366 when the period includes a February 29th, over 365 otherwise.
+    Act365L,
+    /// Business days of the calendar over 252, used in Brazil.
+    Bus252(Arc<dyn Calendar>),
+}
+
+impl PartialEq for DayCount {
+    fn eq(&self, other: &DayCount) -> bool {
+        match (self, other) {
+            (DayCount::ActActIcma(a), DayCount::ActActIcma(b)) => a == b,
+            (DayCount::Bus252(a), DayCount::Bus252(b)) => Arc::ptr_eq(a, b),
+            (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
+        }
+    }
+}
+
+impl DayCount {
+    /// Returns the fraction of a year between `start` and `end`, negative when `end` is before `start`.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use financial::DayCount;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 7, 31).unwrap();
+    /// assert_eq!(DayCount::Thirty360.year_fraction(start, end), 0.5);
+    /// assert_eq!(DayCount::Act360.year_fraction(start, end), 181. / 360.);
+    /// ```
+    pub fn year_fraction(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        if end < start {
+            return -self.year_fraction(end, start);
+        }
+
+        let actual_days = days_between(start, end);
+
+        match self {
+            DayCount::Act365Fixed => actual_days / 365.,
+            DayCount::Act360 => actual_days / 360.,
+            DayCount::Thirty360 => {
+                let d1 = start.day().min(30);
+                let d2 = if end.day() == 31 && d1 == 30 {
+                    30
+                } else {
+                    end.day()
+                };
+
+                thirty_360(start, end, d1, d2)
+            }
+            DayCount::ActActIsda => {
+                let mut fraction = 0.;
+                let mut from = start;
+                while from.year() < end.year() {
+                    let next_year = NaiveDate::from_ymd_opt(from.year() + 1, 1, 1).unwrap();
+                    fraction += days_between(from, next_year) / days_in_year(from.year());
+                    from = next_year;
+                }
+
+                fraction + days_between(from, end) / days_in_year(end.year())
+            }
+            DayCount::ActActIcma(frequency) => {
+                let periods_per_year = frequency.periods_per_year() as f64;
+                let mut periods = 0;
+                let mut period_start = start;
+
+                // full coupon periods count for 1 / frequency each, the rest pro rata of the period it falls in
+                while let Some(period_end) = frequency.advance(start, periods + 1) {
+                    if period_end > end {
+                        let stub = days_between(period_start, end)
+                            / days_between(period_start, period_end);
+                        return (periods as f64 + stub) / periods_per_year;
+                    }
+
+                    periods += 1;
+                    period_start = period_end;
+                }
+
+                f64::NAN
+            }
+            DayCount::Thirty360Us => {
+                let (mut d1, mut d2) = (start.day(), end.day());
+                if is_end_of_february(start) && is_end_of_february(end) {
+                    d2 = 30;
+                }
+                if is_end_of_february(start) {
+                    d1 = 30;
+                }
+                if d2 == 31 && d1 >= 30 {
+                    d2 = 30;
+                }
+
+                thirty_360(start, end, d1.min(30), d2)
+            }
+            DayCount::ThirtyE360 => thirty_360(start, end, start.day().min(30), end.day().min(30)),
+            DayCount::ThirtyE360Isda => {
+                let day = |date: NaiveDate| {
+                    if is_end_of_month(date) {
+                        30
+                    } else {
+                        date.day()
+                    }
+                };
+
+                thirty_360(start, end, day(start), day(end))
+            }
+            DayCount::Act365L => {
+                let includes_leap_day = (start.year()..=end.year())
+                    .filter_map(|year| NaiveDate::from_ymd_opt(year, 2, 29))
+                    .any(|leap_day| start < leap_day && leap_day <= end);
+
+                if includes_leap_day {
+                    actual_days / 366.
+                } else {
+                    actual_days / 365.
+                }
+            }
+            DayCount::Bus252(calendar) => calendar.business_days_between(start, end) as f64 / 252.,
+        }
+    }
+}
+
+pub(crate) fn days_between(start: NaiveDate, end: NaiveDate) -> f64 {
+    end.signed_duration_since(start).num_days() as f64
+}
+
+pub(crate) fn days_in_year(year: i32) -> f64 {
+    if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+        366.
+    } else {
+        365.
+    }
+}
+
+fn is_end_of_month(date: NaiveDate) -> bool {
+    !matches!(date.succ_opt(), Some(next) if next.month() == date.month())
+}
+
+pub(crate) fn is_end_of_february(date: NaiveDate) -> bool {
+    date.month() == 2 && is_end_of_month(date)
+}
+
+pub(crate) fn thirty_360(start: NaiveDate, end: NaiveDate, d1: u32, d2: u32) -> f64 {
+    let days = 360 * (end.year() - start.year())
+        + 30 * (end.month() as i32 - start.month() as i32)
+        + (d2 as i32 - d1 as i32);
+
+    days as f64 / 360.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn act_365_fixed_works() {
+        assert_eq!(
+            DayCount::Act365Fixed.year_fraction(date(2020, 1, 1), date(2021, 1, 1)),
+            366. / 365.
+        );
+    }
+
+    #[test]
+    fn act_360_works() {
+        assert_eq!(
+            DayCount::Act360.year_fraction(date(2021, 1, 1), date(2021, 4, 1)),
+            90. / 360.
+        );
+    }
+
+    #[test]
+    fn thirty_360_works() {
+        let year_fraction = |start, end| DayCount::Thirty360.year_fraction(start, end);
+
+        assert_eq!(
+            year_fraction(date(2021, 1, 15), date(2021, 2, 15)),
+            30. / 360.
+        );
+        assert_eq!(
+            year_fraction(date(2021, 1, 31), date(2021, 3, 31)),
+            60. / 360.
+        );
+        assert_eq!(
+            year_fraction(date(2021, 1, 30), date(2021, 3, 31)),
+            60. / 360.
+        );
+        assert_eq!(
+            year_fraction(date(2021, 1, 15), date(2021, 3, 31)),
+            76. / 360.
+        );
+        assert_eq!(
+            year_fraction(date(2021, 3, 31), date(2021, 1, 31)),
+            -60. / 360.
+        );
+    }
+
+    #[test]
+    fn act_act_isda_works() {
+        let year_fraction = DayCount::ActActIsda.year_fraction(date(2019, 12, 1), date(2020, 3, 1));
+        assert!((year_fraction - (31. / 365. + 60. / 366.)).abs() < 1e-15);
+        assert_eq!(
+            DayCount::ActActIsda.year_fraction(date(2020, 1, 1), date(2022, 1, 1)),
+            2.
+        );
+    }
+
+    #[test]
+    fn act_act_icma_works() {
+        let day_count = DayCount::ActActIcma(Frequency::SemiAnnually);
+
+        assert_eq!(
+            day_count.year_fraction(date(2021, 1, 15), date(2021, 7, 15)),
+            0.5
+        );
+        assert_eq!(
+            day_count.year_fraction(date(2021, 1, 15), date(2022, 1, 15)),
+            1.
+        );
+        // one full period, then 31 days of a 184 days period
+        assert_eq!(
+            day_count.year_fraction(date(2021, 1, 15), date(2021, 8, 15)),
+            (1. + 31. / 184.) / 2.
+        );
+    }
+
+    #[test]
+    fn thirty_360_us_works() {
+        let year_fraction = |start, end| DayCount::Thirty360Us.year_fraction(start, end);
+
+        assert_eq!(
+            year_fraction(date(2021, 2, 28), date(2021, 3, 31)),
+            30. / 360.
+        );
+        assert_eq!(
+            year_fraction(date(2020, 2, 29), date(2021, 2, 28)),
+            360. / 360.
+        );
+        assert_eq!(
+            year_fraction(date(2021, 1, 15), date(2021, 3, 31)),
+            76. / 360.
+        );
+    }
+
+    #[test]
+    fn thirty_e_360_works() {
+        assert_eq!(
+            DayCount::ThirtyE360.year_fraction(date(2021, 1, 15), date(2021, 3, 31)),
+            75. / 360.
+        );
+        assert_eq!(
+            DayCount::ThirtyE360.year_fraction(date(2021, 2, 28), date(2021, 3, 31)),
+            32. / 360.
+        );
+        assert_eq!(
+            DayCount::ThirtyE360Isda.year_fraction(date(2021, 2, 28), date(2021, 3, 31)),
+            30. / 360.
+        );
+        assert_eq!(
+            DayCount::ThirtyE360Isda.year_fraction(date(2021, 1, 15), date(2021, 4, 30)),
+            105. / 360.
+        );
+    }
+
+    #[test]
+    fn act_365_l_works() {
+        assert_eq!(
+            DayCount::Act365L.year_fraction(date(2020, 1, 1), date(2020, 3, 1)),
+            60. / 366.
+        );
+        assert_eq!(
+            DayCount::Act365L.year_fraction(date(2020, 3, 1), date(2020, 6, 1)),
+            92. / 365.
+        );
+    }
+
+    #[test]
+    fn bus_252_counts_business_days() {
+        let calendar = crate::calendar::HolidayCalendar::new(&[date(2021, 12, 24)]);
+        let day_count = DayCount::Bus252(Arc::new(calendar));
+
+        assert_eq!(
+            day_count.year_fraction(date(2021, 12, 20), date(2021, 12, 27)),
+            4. / 252.
+        );
+        assert_eq!(day_count, day_count.clone());
+        assert_ne!(day_count, DayCount::Act360);
+    }
+}
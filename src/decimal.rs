@@ -0,0 +1,63 @@
+//! `Decimal` versions of the functions whose results end up in ledgers, enabled by the `decimal` feature.
+//!
+//! Amounts and rates are `rust_decimal::Decimal`s, and periods are whole numbers so that compounding is exact,
+//! e.g. `fv` returns `-2221.02` where `financial::fv` returns `-2221.020000000001`.
+//! Results with more than 28 significant digits are rounded, and results out of the range of `Decimal` are errors.
+//!
+//! IRR is still solved in `f64`, and returned as a `Decimal`.
+//!
+//! # Example
+//! ```
+//! use financial::decimal::{self, Decimal};
+//!
+//! let rate = Decimal::new(1, 1);
+//! let fv = decimal::fv(rate, 5, Some(Decimal::from(100)), Some(Decimal::from(1000)), None).unwrap();
+//! assert_eq!(fv, Decimal::new(-222102, 2));
+//! ```
+
+mod amortization;
+mod fv;
+mod irr;
+mod npv;
+mod pmt;
+mod pv;
+mod round;
+
+pub use crate::decimal::amortization::{
+    amortization_schedule, AmortizationRow, AmortizationSchedule,
+};
+pub use crate::decimal::fv::fv;
+pub use crate::decimal::irr::{irr, irr_with_options};
+pub use crate::decimal::npv::npv;
+pub use crate::decimal::pmt::pmt;
+pub use crate::decimal::pv::pv;
+pub use crate::decimal::round::{round_bankers, round_half_up};
+use crate::error::FinancialError;
+pub use rust_decimal::Decimal;
+
+// Decimal operators panic when a result is out of range, which only realistically happens when compounding
+// a large rate over many periods, so checked operations report it as an invalid rate.
+fn checked(result: Option<Decimal>) -> Result<Decimal, FinancialError> {
+    result.ok_or(FinancialError::InvalidArgument {
+        name: "rate",
+        reason: "compounds beyond the range of Decimal",
+    })
+}
+
+// (1 + rate) ^ nper
+fn compound(rate: Decimal, nper: u32) -> Result<Decimal, FinancialError> {
+    let base = checked(Decimal::ONE.checked_add(rate))?;
+
+    (0..nper).try_fold(Decimal::ONE, |factor, _| checked(factor.checked_mul(base)))
+}
+
+fn validate_rate(rate: Decimal) -> Result<(), FinancialError> {
+    if rate <= -Decimal::ONE {
+        return Err(FinancialError::InvalidArgument {
+            name: "rate",
+            reason: "must be greater than -1",
+        });
+    }
+
+    Ok(())
+}
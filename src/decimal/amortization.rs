@@ -0,0 +1,206 @@
+use crate::decimal::{checked, pmt::pmt, round::round_half_up, validate_rate};
+use crate::error::FinancialError;
+use rust_decimal::Decimal;
+
+/// A single period of a decimal amortization schedule.
+///
+/// Amounts follow the same sign convention as `financial::amortization_schedule()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmortizationRow {
+    /// The period number, from 1 to `nper`.
+    pub period: u32,
+    /// The payment made in this period, rounded to `minor_units`.
+    pub payment: Decimal,
+    /// The interest accrued over this period, rounded to `minor_units`.
+    pub interest: Decimal,
+    /// The part of the payment that repays the loan.
+    pub principal: Decimal,
+    /// The balance outstanding after this period's payment.
+    pub balance: Decimal,
+}
+
+/// The rows of a decimal amortization schedule, one per period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmortizationSchedule {
+    /// The periods, in order.
+    pub rows: Vec<AmortizationRow>,
+}
+
+impl AmortizationSchedule {
+    /// Returns the exact sum of all payments.
+    pub fn total_payment(&self) -> Decimal {
+        self.rows.iter().map(|row| row.payment).sum()
+    }
+
+    /// Returns the exact sum of the interest paid over the whole loan.
+    pub fn total_interest(&self) -> Decimal {
+        self.rows.iter().map(|row| row.interest).sum()
+    }
+
+    /// Returns the exact sum of the principal repaid over the whole loan.
+    pub fn total_principal(&self) -> Decimal {
+        self.rows.iter().map(|row| row.principal).sum()
+    }
+}
+
+/// Returns the amortization schedule of a level-payment loan like `financial::amortization_schedule()`,
+/// with every amount in `minor_units` decimal places.
+///
+/// Payments and interest are rounded half up, and the last row is adjusted so the balance closes at the
+/// balloon `fv` (zero by default). When payments are made at the beginning of each period, it closes at the
+/// balloon discounted by the last period instead, rounded to `minor_units`. Totals therefore reconcile to the minor unit.
+///
+/// # Example
+/// ```
+/// use financial::decimal::Decimal;
+///
+/// let rate = Decimal::new(1, 2);
+/// let schedule =
+///     financial::decimal::amortization_schedule(rate, 12, Decimal::from(1000), None, None, 2).unwrap();
+/// assert_eq!(schedule.rows[0].payment, Decimal::new(-8885, 2));
+/// assert_eq!(schedule.rows[11].payment, Decimal::new(-8884, 2));
+/// assert_eq!(schedule.rows[11].balance, Decimal::ZERO);
+/// assert_eq!(schedule.total_principal(), Decimal::from(-1000));
+/// ```
+pub fn amortization_schedule(
+    rate: Decimal,
+    nper: u32,
+    pv: Decimal,
+    fv: Option<Decimal>,
+    pmt_at_begining: Option<bool>,
+    minor_units: u32,
+) -> Result<AmortizationSchedule, FinancialError> {
+    validate_rate(rate)?;
+
+    let round = |x: Decimal| round_half_up(x, minor_units);
+
+    let at_begining = pmt_at_begining.unwrap_or(false);
+    let payment = round(pmt(rate, nper, Some(pv), fv, pmt_at_begining)?);
+
+    // when paying at the beginning, the last period still accrues interest on what's left
+    let fv = fv.unwrap_or_default();
+    let closing_balance = if at_begining {
+        round(-checked(fv.checked_div(Decimal::ONE + rate))?)
+    } else {
+        -fv
+    };
+
+    let mut balance = pv;
+    let rows = (1..=nper)
+        .map(|period| {
+            let interest = if at_begining && period == 1 {
+                Decimal::ZERO
+            } else {
+                round(-checked(balance.checked_mul(rate))?)
+            };
+
+            let principal = if period == nper {
+                closing_balance - balance
+            } else {
+                payment - interest
+            };
+
+            balance = if period == nper {
+                closing_balance
+            } else {
+                balance + principal
+            };
+
+            Ok(AmortizationRow {
+                period,
+                payment: interest + principal,
+                interest,
+                principal,
+                balance,
+            })
+        })
+        .collect::<Result<_, FinancialError>>()?;
+
+    Ok(AmortizationSchedule { rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn amortization_schedule_reconciles_to_the_cent() {
+        let rate = dec("0.09") / dec("12");
+        let schedule = amortization_schedule(rate, 360, dec("125000"), None, None, 2).unwrap();
+
+        assert_eq!(schedule.rows.len(), 360);
+        assert_eq!(schedule.rows[0].payment, dec("-1005.78"));
+        assert_eq!(schedule.rows[0].interest, dec("-937.50"));
+        assert_eq!(schedule.rows[359].balance, Decimal::ZERO);
+        assert_eq!(schedule.total_principal(), dec("-125000"));
+        assert_eq!(
+            schedule.total_payment(),
+            schedule.total_principal() + schedule.total_interest()
+        );
+
+        for row in schedule.rows.iter() {
+            assert_eq!(
+                row.payment.round_dp(2),
+                row.payment,
+                "period {}",
+                row.period
+            );
+            assert_eq!(
+                row.balance.round_dp(2),
+                row.balance,
+                "period {}",
+                row.period
+            );
+        }
+    }
+
+    #[test]
+    fn amortization_schedule_matches_f64() {
+        let schedule = amortization_schedule(
+            dec("0.05"),
+            10,
+            dec("1000"),
+            Some(dec("-200")),
+            Some(true),
+            2,
+        )
+        .unwrap();
+        let expected =
+            crate::amortization_schedule(0.05, 10, 1000., Some(-200.), Some(true), Some(2))
+                .unwrap();
+
+        // unlike the f64 schedule, the balance left before the balloon is rounded too
+        assert_eq!(schedule.rows[9].balance, dec("190.48"));
+
+        for (row, expected) in schedule.rows.iter().zip(expected.rows.iter()).take(9) {
+            let close = |x: Decimal, y: f64| {
+                (x - Decimal::from_f64_retain(y).unwrap()).abs() < dec("0.0000001")
+            };
+            assert!(
+                close(row.payment, expected.payment),
+                "period {}",
+                row.period
+            );
+            assert!(
+                close(row.interest, expected.interest),
+                "period {}",
+                row.period
+            );
+            assert!(
+                close(row.balance, expected.balance),
+                "period {}",
+                row.period
+            );
+        }
+    }
+
+    #[test]
+    fn amortization_schedule_fails_on_invalid_arguments() {
+        assert!(amortization_schedule(dec("0.01"), 0, dec("1000"), None, None, 2).is_err());
+        assert!(amortization_schedule(dec("-1"), 12, dec("1000"), None, None, 2).is_err());
+    }
+}
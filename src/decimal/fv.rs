@@ -0,0 +1,90 @@
+use crate::decimal::{checked, compound};
+use crate::error::FinancialError;
+use rust_decimal::Decimal;
+
+/// Calculates the future value of an investment like `financial::fv()`, in exact decimal arithmetic
+/// over a whole number of periods.
+///
+/// # Example
+/// ```
+/// use financial::decimal::Decimal;
+///
+/// let rate = Decimal::new(1, 1);
+/// let (pmt, pv) = (Decimal::from(100), Decimal::from(1000));
+/// let fv = financial::decimal::fv(rate, 5, Some(pmt), Some(pv), Some(false)).unwrap();
+/// assert_eq!(fv, Decimal::new(-222102, 2));
+/// ```
+pub fn fv(
+    rate: Decimal,
+    nper: u32,
+    pmt: Option<Decimal>,
+    pv: Option<Decimal>,
+    pmt_at_begining: Option<bool>,
+) -> Result<Decimal, FinancialError> {
+    let pmt = pmt.unwrap_or_default();
+    let pv = pv.unwrap_or_default();
+
+    if rate.is_zero() {
+        let payments = checked(pmt.checked_mul(Decimal::from(nper)))?;
+        return Ok(-checked(pv.checked_add(payments))?);
+    }
+
+    let factor = compound(rate, nper)?;
+    let pmt_at_begining = pmt_at_begining.unwrap_or(false);
+    let pmt = if pmt_at_begining {
+        checked(pmt.checked_mul(Decimal::ONE + rate))?
+    } else {
+        pmt
+    };
+
+    let pv_fv = checked(pv.checked_mul(factor))?;
+    let pmt_fv = checked(
+        pmt.checked_mul(factor - Decimal::ONE)
+            .and_then(|x| x.checked_div(rate)),
+    )?;
+    Ok(-checked(pv_fv.checked_add(pmt_fv))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn fv_is_exact() {
+        let fv =
+            |pv, at_begining| fv(dec("0.1"), 5, Some(dec("100")), pv, Some(at_begining)).unwrap();
+
+        assert_eq!(fv(Some(dec("1000")), false), dec("-2221.02"));
+        assert_eq!(fv(Some(dec("1000")), true), dec("-2282.071"));
+        assert_eq!(fv(None, false), dec("-610.51"));
+    }
+
+    #[test]
+    fn fv_matches_f64() {
+        let fv_f64 = crate::fv(0.0075, 360., Some(-500.), Some(10000.), Some(true));
+        let fv = fv(
+            dec("0.0075"),
+            360,
+            Some(dec("-500")),
+            Some(dec("10000")),
+            Some(true),
+        )
+        .unwrap();
+        assert!((fv - Decimal::from_f64_retain(fv_f64).unwrap()).abs() < dec("0.000001"));
+    }
+
+    #[test]
+    fn fv_works_with_zero_rate() {
+        let fv = fv(Decimal::ZERO, 5, Some(dec("100")), Some(dec("1000")), None);
+        assert_eq!(fv, Ok(dec("-1500")));
+    }
+
+    #[test]
+    fn fv_fails_on_overflow() {
+        assert!(fv(dec("10"), 100, None, Some(dec("1")), None).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+use crate::error::FinancialError;
+use crate::periodic_cashflow::irr::irr_with_options as irr_f64;
+use crate::solver::SolverOptions;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+/// Calculates the internal rate of return of a series of periodic cash flows like `financial::irr()`.
+///
+/// The rate is solved in `f64`, so it's only as precise as `financial::irr()`, and returned as a `Decimal`.
+///
+/// # Example
+/// ```
+/// use financial::decimal::Decimal;
+///
+/// let cf = [Decimal::from(-1000), Decimal::from(1100)];
+/// let irr = financial::decimal::irr(&cf, None).unwrap();
+/// assert_eq!(irr.round_dp(7), Decimal::new(1, 1));
+/// ```
+pub fn irr(values: &[Decimal], guess: Option<Decimal>) -> Result<Decimal, FinancialError> {
+    irr_with_options(values, guess, &SolverOptions::default())
+}
+
+/// Calculates the internal rate of return like `irr`, with the solver tuned by `options`.
+pub fn irr_with_options(
+    values: &[Decimal],
+    guess: Option<Decimal>,
+    options: &SolverOptions,
+) -> Result<Decimal, FinancialError> {
+    let values: Vec<f64> = values.iter().map(to_f64).collect();
+    let guess = guess.as_ref().map(to_f64);
+
+    let rate = irr_f64(&values, guess, options)?;
+    Decimal::from_f64(rate).ok_or(FinancialError::NoSolution)
+}
+
+fn to_f64(value: &Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn irr_matches_f64() {
+        let cf = [-500., 100., 100., 100., 100.];
+        let cf_decimal: Vec<Decimal> = cf.iter().map(|v| Decimal::from_f64(*v).unwrap()).collect();
+
+        let irr = irr(&cf_decimal, None).unwrap();
+        let expected = crate::irr(&cf, None).unwrap();
+        assert!(
+            (irr.to_f64().unwrap() - expected).abs() <= PRECISION,
+            "got {}",
+            irr
+        );
+    }
+
+    #[test]
+    fn irr_fails_like_f64() {
+        assert_eq!(
+            irr(&[dec("100"), dec("100")], None),
+            Err(FinancialError::NoSignChange)
+        );
+        assert!(irr(&[], None).is_err());
+    }
+}
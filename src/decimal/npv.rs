@@ -0,0 +1,67 @@
+use crate::decimal::{checked, validate_rate};
+use crate::error::FinancialError;
+use rust_decimal::Decimal;
+
+/// Calculates the net present value of a series of periodic cash flows like `financial::npv()`,
+/// in decimal arithmetic.
+///
+/// # Example
+/// ```
+/// use financial::decimal::Decimal;
+///
+/// let cf = [Decimal::from(-110), Decimal::from(242)];
+/// let npv = financial::decimal::npv(Decimal::new(1, 1), &cf).unwrap();
+/// assert_eq!(npv, Decimal::from(100));
+/// ```
+pub fn npv(rate: Decimal, values: &[Decimal]) -> Result<Decimal, FinancialError> {
+    validate_rate(rate)?;
+
+    let base = checked(Decimal::ONE.checked_add(rate))?;
+    let mut factor = Decimal::ONE;
+    values.iter().try_fold(Decimal::ZERO, |npv, value| {
+        factor = checked(factor.checked_mul(base))?;
+        checked(value.checked_div(factor).and_then(|x| npv.checked_add(x)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn npv_matches_f64() {
+        let cf = [-1000., 500., 500., 500.];
+        let cf_decimal: Vec<Decimal> = cf
+            .iter()
+            .map(|v| Decimal::from_f64_retain(*v).unwrap())
+            .collect();
+
+        for rate in ["-0.5", "0", "0.1", "2"].iter() {
+            let npv = npv(dec(rate), &cf_decimal).unwrap();
+            let expected = crate::npv(rate.parse().unwrap(), &cf);
+            assert!(
+                (npv - Decimal::from_f64_retain(expected).unwrap()).abs() < dec("0.0000001"),
+                "rate {}: got {}, expected {}",
+                rate,
+                npv,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn npv_is_exact_when_discounting_is() {
+        let cf = [dec("110"), dec("121"), dec("-133.1")];
+        assert_eq!(npv(dec("0.1"), &cf), Ok(dec("100")));
+    }
+
+    #[test]
+    fn npv_fails_on_invalid_rate() {
+        assert!(npv(dec("-1"), &[dec("100")]).is_err());
+        assert!(npv(Decimal::MAX, &[dec("100")]).is_err());
+    }
+}
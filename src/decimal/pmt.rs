@@ -0,0 +1,100 @@
+use crate::decimal::{checked, compound};
+use crate::error::FinancialError;
+use rust_decimal::Decimal;
+
+/// Calculates the payment for a loan like `financial::pmt()`, in exact decimal arithmetic
+/// over a whole number of periods.
+///
+/// The payment isn't rounded, see `round_half_up` and `round_bankers`, or `amortization_schedule`
+/// for payments in minor units that repay the loan exactly.
+///
+/// # Example
+/// ```
+/// use financial::decimal::{round_half_up, Decimal};
+///
+/// let rate = Decimal::new(1, 2);
+/// let pmt = financial::decimal::pmt(rate, 12, Some(Decimal::from(1000)), None, None).unwrap();
+/// assert_eq!(round_half_up(pmt, 2), Decimal::new(-8885, 2));
+/// ```
+pub fn pmt(
+    rate: Decimal,
+    nper: u32,
+    pv: Option<Decimal>,
+    fv: Option<Decimal>,
+    pmt_at_begining: Option<bool>,
+) -> Result<Decimal, FinancialError> {
+    if nper == 0 {
+        return Err(FinancialError::InvalidArgument {
+            name: "nper",
+            reason: "must be greater than zero",
+        });
+    }
+
+    let pv = pv.unwrap_or_default();
+    let fv = fv.unwrap_or_default();
+
+    if rate.is_zero() {
+        return Ok(-checked(pv.checked_add(fv))? / Decimal::from(nper));
+    }
+
+    let factor = compound(rate, nper)?;
+    let annuity_factor = if pmt_at_begining.unwrap_or(false) {
+        checked((Decimal::ONE + rate).checked_mul(factor - Decimal::ONE))?
+    } else {
+        factor - Decimal::ONE
+    };
+
+    let balance = checked(pv.checked_mul(factor).and_then(|x| x.checked_add(fv)))?;
+    checked(
+        (-balance)
+            .checked_mul(rate)
+            .and_then(|x| x.checked_div(annuity_factor)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn pmt_matches_f64() {
+        for at_begining in [false, true].iter() {
+            let expected = crate::pmt(
+                0.08 / 12.,
+                10.,
+                Some(10000.),
+                Some(-500.),
+                Some(*at_begining),
+            );
+            let pmt = pmt(
+                dec("0.08") / dec("12"),
+                10,
+                Some(dec("10000")),
+                Some(dec("-500")),
+                Some(*at_begining),
+            )
+            .unwrap();
+            assert!(
+                (pmt - Decimal::from_f64_retain(expected).unwrap()).abs() < dec("0.0000001"),
+                "got {}, expected {}",
+                pmt,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn pmt_works_with_zero_rate() {
+        let pmt = pmt(Decimal::ZERO, 4, Some(dec("1000")), Some(dec("200")), None);
+        assert_eq!(pmt, Ok(dec("-300")));
+    }
+
+    #[test]
+    fn pmt_fails_without_periods() {
+        assert!(pmt(dec("0.1"), 0, Some(dec("1000")), None, None).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::decimal::{checked, compound};
+use crate::error::FinancialError;
+use rust_decimal::Decimal;
+
+/// Calculates the present value of a loan or an investment like `financial::pv()`, in exact decimal arithmetic
+/// over a whole number of periods.
+///
+/// # Example
+/// ```
+/// use financial::decimal::Decimal;
+///
+/// let rate = Decimal::new(1, 1);
+/// let pv = financial::decimal::pv(rate, 2, None, Some(Decimal::from(-121)), None).unwrap();
+/// assert_eq!(pv, Decimal::from(100));
+/// ```
+pub fn pv(
+    rate: Decimal,
+    nper: u32,
+    pmt: Option<Decimal>,
+    fv: Option<Decimal>,
+    pmt_at_begining: Option<bool>,
+) -> Result<Decimal, FinancialError> {
+    let pmt = pmt.unwrap_or_default();
+    let fv = fv.unwrap_or_default();
+
+    if rate.is_zero() {
+        let payments = checked(pmt.checked_mul(Decimal::from(nper)))?;
+        return Ok(-checked(fv.checked_add(payments))?);
+    }
+
+    let factor = compound(rate, nper)?;
+    let pmt = if pmt_at_begining.unwrap_or(false) {
+        checked(pmt.checked_mul(Decimal::ONE + rate))?
+    } else {
+        pmt
+    };
+
+    let pmt_fv = checked(
+        pmt.checked_mul(factor - Decimal::ONE)
+            .and_then(|x| x.checked_div(rate)),
+    )?;
+    Ok(-checked(
+        fv.checked_add(pmt_fv).and_then(|x| x.checked_div(factor)),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn pv_matches_f64() {
+        let cases = [(false, -1000.0000000000001), (true, -1037.9078676940845)];
+
+        for (at_begining, expected) in cases.iter() {
+            let pv = pv(
+                dec("0.1"),
+                5,
+                Some(dec("100")),
+                Some(dec("1000")),
+                Some(*at_begining),
+            )
+            .unwrap();
+            assert!(
+                (pv - Decimal::from_f64_retain(*expected).unwrap()).abs() < dec("0.0000001"),
+                "got {}",
+                pv
+            );
+        }
+    }
+
+    #[test]
+    fn pv_inverts_fv() {
+        let fv = crate::decimal::fv(dec("0.05"), 10, Some(dec("-100")), Some(dec("2000")), None);
+        let pv = pv(dec("0.05"), 10, Some(dec("-100")), fv.ok(), None).unwrap();
+        assert_eq!(pv.round_dp(20), dec("2000"));
+    }
+
+    #[test]
+    fn pv_works_with_zero_rate() {
+        let pv = pv(Decimal::ZERO, 5, Some(dec("100")), Some(dec("1000")), None);
+        assert_eq!(pv, Ok(dec("-1500")));
+    }
+}
@@ -0,0 +1,60 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// Rounds `value` to `minor_units` decimal places, rounding midpoints to the even neighbour.
+///
+/// Also known as banker's rounding, it doesn't bias totals of many rounded amounts upwards.
+///
+/// # Example
+/// ```
+/// use financial::decimal::{round_bankers, Decimal};
+///
+/// assert_eq!(round_bankers(Decimal::new(12345, 3), 2), Decimal::new(1234, 2));
+/// assert_eq!(round_bankers(Decimal::new(12355, 3), 2), Decimal::new(1236, 2));
+/// ```
+pub fn round_bankers(value: Decimal, minor_units: u32) -> Decimal {
+    value.round_dp_with_strategy(minor_units, RoundingStrategy::MidpointNearestEven)
+}
+
+/// Rounds `value` to `minor_units` decimal places, rounding midpoints away from zero.
+///
+/// This is the commercial rounding of most currencies, where half a cent is rounded up to a cent,
+/// and half a cent owed to a cent owed.
+///
+/// # Example
+/// ```
+/// use financial::decimal::{round_half_up, Decimal};
+///
+/// assert_eq!(round_half_up(Decimal::new(12345, 3), 2), Decimal::new(1235, 2));
+/// assert_eq!(round_half_up(Decimal::new(-12345, 3), 2), Decimal::new(-1235, 2));
+/// ```
+pub fn round_half_up(value: Decimal, minor_units: u32) -> Decimal {
+    value.round_dp_with_strategy(minor_units, RoundingStrategy::MidpointAwayFromZero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn rounding_differs_on_midpoints_only() {
+        let cases = [
+            ("2.345", "2.34", "2.35"),
+            ("2.355", "2.36", "2.36"),
+            ("-2.345", "-2.34", "-2.35"),
+            ("2.3451", "2.35", "2.35"),
+            ("2.344", "2.34", "2.34"),
+            ("2.3", "2.3", "2.3"),
+        ];
+
+        for (value, bankers, half_up) in cases.iter() {
+            assert_eq!(round_bankers(dec(value), 2), dec(bankers), "{}", value);
+            assert_eq!(round_half_up(dec(value), 2), dec(half_up), "{}", value);
+        }
+        assert_eq!(round_half_up(dec("2.5"), 0), dec("3"));
+        assert_eq!(round_bankers(dec("2.5"), 0), dec("2"));
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+
+/// The error returned by fallible functions of this crate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FinancialError {
+    /// The values and dates of a schedule don't have the same length.
+    LengthMismatch { values: usize, dates: usize },
+    /// The first date of a schedule isn't the earliest.
+    UnsortedDates,
+    /// The cashflow doesn't include both positive and negative values, so it has no rate of return.
+    NoSignChange,
+    /// The root finder gave up after `iterations`, `last_estimate` being its closest attempt.
+    DidNotConverge { iterations: u32, last_estimate: f64 },
+    /// No value satisfies the arguments provided.
+    NoSolution,
+    /// The argument `name` is out of its valid domain.
+    InvalidArgument {
+        name: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for FinancialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FinancialError::LengthMismatch { values, dates } => write!(
+                f,
+                "values and dates length must match, got {} values and {} dates",
+                values, dates
+            ),
+            FinancialError::UnsortedDates => write!(f, "first date must be the earliest"),
+            FinancialError::NoSignChange => {
+                write!(f, "cashflow must include positive and negative values")
+            }
+            FinancialError::DidNotConverge {
+                iterations,
+                last_estimate,
+            } => write!(
+                f,
+                "couldn't find a root after {} iterations, last estimate was {}",
+                iterations, last_estimate
+            ),
+            FinancialError::NoSolution => write!(f, "no value satisfies the arguments provided"),
+            FinancialError::InvalidArgument { name, reason } => {
+                write!(f, "invalid argument `{}`: {}", name, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FinancialError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_works() {
+        let err = FinancialError::InvalidArgument {
+            name: "per",
+            reason: "must be between 1 and nper",
+        };
+        assert_eq!(
+            err.to_string(),
+            "invalid argument `per`: must be between 1 and nper"
+        );
+
+        let err = FinancialError::LengthMismatch {
+            values: 2,
+            dates: 3,
+        };
+        assert_eq!(
+            err.to_string(),
+            "values and dates length must match, got 2 values and 3 dates"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use crate::common::utils;
+use chrono::{Duration, NaiveDate};
+
+/// How often a periodic payment occurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Weekly,
+    Biweekly,
+    Monthly,
+    Quarterly,
+    SemiAnnually,
+    Annually,
+}
+
+impl Frequency {
+    pub fn periods_per_year(&self) -> u32 {
+        match self {
+            Frequency::Weekly => 52,
+            Frequency::Biweekly => 26,
+            Frequency::Monthly => 12,
+            Frequency::Quarterly => 4,
+            Frequency::SemiAnnually => 2,
+            Frequency::Annually => 1,
+        }
+    }
+
+    /// Returns the date `n` periods after `date`, or `None` if it's out of range.
+    ///
+    /// Month based frequencies keep the day of month of `date`, clamped to the end of shorter months.
+    pub fn advance(&self, date: NaiveDate, n: i32) -> Option<NaiveDate> {
+        match self {
+            Frequency::Weekly => date.checked_add_signed(Duration::weeks(n as i64)),
+            Frequency::Biweekly => date.checked_add_signed(Duration::weeks(2 * n as i64)),
+            Frequency::Monthly => utils::add_months(date, n),
+            Frequency::Quarterly => utils::add_months(date, 3 * n),
+            Frequency::SemiAnnually => utils::add_months(date, 6 * n),
+            Frequency::Annually => utils::add_months(date, 12 * n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn advance_clamps_to_end_of_month() {
+        let start = date(2021, 1, 31);
+
+        assert_eq!(
+            Frequency::Monthly.advance(start, 1),
+            Some(date(2021, 2, 28))
+        );
+        assert_eq!(
+            Frequency::Monthly.advance(start, 2),
+            Some(date(2021, 3, 31))
+        );
+        assert_eq!(
+            Frequency::Quarterly.advance(start, 1),
+            Some(date(2021, 4, 30))
+        );
+        assert_eq!(
+            Frequency::Annually.advance(date(2020, 2, 29), 1),
+            Some(date(2021, 2, 28))
+        );
+        assert_eq!(
+            Frequency::Monthly.advance(start, -2),
+            Some(date(2020, 11, 30))
+        );
+    }
+
+    #[test]
+    fn advance_works_with_weeks() {
+        let start = date(2021, 1, 1);
+
+        assert_eq!(Frequency::Weekly.advance(start, 1), Some(date(2021, 1, 8)));
+        assert_eq!(
+            Frequency::Biweekly.advance(start, 2),
+            Some(date(2021, 1, 29))
+        );
+    }
+}
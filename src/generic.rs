@@ -0,0 +1,27 @@
+//! Versions of the core functions for any floating point type implementing `num_traits::Float`, such as `f32`.
+//!
+//! They take and return the same arguments as their `f64` counterparts at the crate root, which call them.
+//! Solvers keep their estimates in `f64`, like the rates and tolerances of `SolverOptions`, and evaluate the
+//! function in the given type. For types less precise than `f64`, `irr` and `xirr` accept a root once NPV is
+//! within the rounding error of the cashflows, while the functions taking `SolverOptions` use them as given.
+//!
+//! # Example
+//! ```
+//! let cf = [-500_f32, 100., 100., 100., 100.];
+//! let irr = financial::generic::irr(&cf, None).unwrap();
+//! assert!(financial::generic::npv(irr, &cf).abs() < 1e-4);
+//! ```
+
+pub use crate::common::find_root::find_root_generic as find_root;
+pub use crate::periodic_cashflow::fv::fv_generic as fv;
+pub use crate::periodic_cashflow::irr::{
+    irr_generic as irr, irr_generic_with_options as irr_with_options,
+};
+pub use crate::periodic_cashflow::mirr::mirr_generic as mirr;
+pub use crate::periodic_cashflow::npv::npv_generic as npv;
+pub use crate::periodic_cashflow::pv::pv_generic as pv;
+pub use crate::scheduled_cashflow::xirr::{
+    xirr_generic as xirr, xirr_generic_with_options as xirr_with_options,
+};
+pub use crate::scheduled_cashflow::xnpv::xnpv_generic as xnpv;
+pub use num_traits::Float;
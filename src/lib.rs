@@ -6,27 +6,111 @@
 //!
 //! It supports both periodic and scheduled computation for [IRR](https://en.wikipedia.org/wiki/Internal_rate_of_return) and [NPV](https://en.wikipedia.org/wiki/Net_present_value).
 //!
-//! IRR and NPV functions are faster since powers are pre-computed iteratively instead of using power function multiple times. Take this with a grain of salt since no benches tests are offered at the moment.
+//! IRR, MIRR and NPV functions are faster since discount factors are accumulated iteratively, without allocating, instead of using power function multiple times. `benches/benches.rs` times NPV against the previous approach of pre-computing every power.
 //!
 //! ## Supported Functions
 //!
 //! - FV(Rate, Nper, Pmt, Pv, Pmt_is_due)
 //! - PV(Rate, Nper, Pmt, Fv, Pmt_is_due)
+//! - PMT(Rate, Nper, Pv, Fv, Pmt_is_due)
+//! - IPMT(Rate, Per, Nper, Pv, Fv, Pmt_is_due)
+//! - PPMT(Rate, Per, Nper, Pv, Fv, Pmt_is_due)
+//! - NPER(Rate, Pmt, Pv, Fv, Pmt_is_due)
+//! - RATE(Nper, Pmt, Pv, Fv, Pmt_is_due, Guess)
+//! - CUMIPMT(Rate, Nper, Pv, Start_period, End_period, Pmt_is_due)
+//! - CUMPRINC(Rate, Nper, Pv, Start_period, End_period, Pmt_is_due)
+//! - EFFECT(Nominal_rate, Npery)
+//! - NOMINAL(Effect_rate, Npery)
 //! - NPV(Rate, values)
 //! - XNPV(Rate), values, dates)
 //! - IRR(values)
 //! - XIRR(values, dates)
 //! - MIRR(values, finance_rate, reinvest_rate)
+//! - EDATE(Start_date, Months)
+//! - EOMONTH(Start_date, Months)
+//! - WORKDAY(Start_date, Days, Holidays)
+//! - WORKDAY.INTL(Start_date, Days, Weekend, Holidays)
+//! - NETWORKDAYS(Start_date, End_date, Holidays)
+//! - NETWORKDAYS.INTL(Start_date, End_date, Weekend, Holidays)
+//!
+//! ## Errors
+//!
+//! - Fallible functions return a `financial::FinancialError`, which tells apart invalid arguments, mismatched schedules,
+//!   cashflows without a sign change, and a solver that didn't converge.
+//!
+//! ## Solver Options
+//!
+//! - `financial::irr_with_options()`, `financial::xirr_with_options()` and `financial::rate_with_options()` take a `SolverOptions`
+//!   to tune the tolerances, the iteration limits of each phase, the bracket search and the range of acceptable rates.
+//! - By default IRR and XIRR use Newton's method with the analytic derivative of NPV, falling back to Brent's method
+//!   on a bracket around the guess. `SolverMethod::NewtonBisection` keeps the previous finite difference and bisection solver.
+//! - `financial::irr_with_report()` and `financial::xirr_with_report()` return a `SolveReport` telling which phase the solver ended in,
+//!   its iterations, residual and bracket, and optionally every estimate, to diagnose a rate of return that couldn't be found.
+//!
+//! ## Multiple IRRs
+//!
+//! - `financial::irr_all()` and `financial::xirr_all()` return every rate of return within a range for cashflows with several sign changes,
+//!   and whether the cashflow changes sign once, which by Descartes' rule of signs leaves a single rate of return above -100%.
+//!   `IrrRoots::is_unique()` tells whether exactly one rate was found within the range.
+//!
+//! ## Rate Conversion
+//!
+//! - `financial::convert_rate()` converts an annual rate between any two `Compounding` frequencies, including continuous compounding,
+//!   so that consistent rates can be fed into `npv`, `xnpv`, `fv` and `pv`.
+//!
+//! ## Day Count Conventions
+//!
+//! - `financial::xnpv_with()` and `financial::xirr_with()` measure the time between dates with a `DayCount` convention
+//!   (ACT/365F, ACT/360, ACT/ACT ISDA, ACT/ACT ICMA, 30/360, 30/360 US, 30E/360, 30E/360 ISDA, ACT/365L or BUS/252 on a `Calendar`)
+//!   instead of the actual days over 365 used by `xnpv` and `xirr`, like Excel.
+//! - `financial::naive_date::yearfrac()` reproduces Excel's `YEARFRAC`, quirks included, for each `Basis` (0 to 4, convertible from Excel's `basis` argument with `Basis::try_from()` and into a `DayCount` with `DayCount::from()`).
+//!
+//! ## Business Days
+//!
+//! - `HolidayCalendar` is a `Calendar` of holidays, loadable from a file with one `YYYY-MM-DD` date per line, and a `Weekend` that defaults to Saturday and Sunday.
+//! - `Calendar::adjust()` and `Calendar::adjust_schedule()` move dates that aren't business days according to a `BusinessDayConvention` (Following, Modified Following, Preceding, Modified Preceding or Unadjusted), before they're fed to `naive_date::xnpv` or `naive_date::xirr`.
+//! - `financial::naive_date::edate()`, `eomonth()`, `workday()`, `workday_intl()`, `networkdays()` and `networkdays_intl()` build the `NaiveDate` schedules consumed by `naive_date::xnpv` and `naive_date::xirr`, with Excel's month-end clamping.
+//! - The `.INTL` variants take a `Weekend`, converted from Excel's weekend numbers with `Weekend::try_from()` or parsed from its `"0000011"` masks.
+//! - `financial::naive_date::schedule()` builds ISDA style schedules from an effective date, a termination date and a `Frequency`, with a `StubRule`, an end-of-month rule, and dates adjusted to business days of a `Calendar`.
+//!
+//! ## Cashflow Schedules
+//!
+//! - `financial::naive_date::CashflowSchedule` owns the `(date, amount)` entries of a schedule, built from parallel slices, iterators of tuples or maps. It sorts and merges same-day flows, splits at a date, and provides `xnpv`, `xirr` and `total`.
+//! - `CashflowSchedule::normalized()` accepts flows in arbitrary order, such as ledger exports, sorting them by date and optionally aggregating same-date flows, and `CashflowSchedule::xnpv_at()` values them at a caller-chosen valuation date instead of the first entry.
+//! - `financial::xnpv_at()` values a schedule at a reporting date before, inside or after its cash flows, compounding past flows forward and discounting future ones, and `financial::xfv()` gives its future value at a horizon date. Both accept dates in any order and have `naive_date` and `CashflowSchedule` counterparts.
+//!
+//! ## Amortization
+//!
+//! - `financial::amortization_schedule()` produces the period by period payment, interest, principal and remaining balance of a level-payment loan.
+//! - `financial::loan_schedule()` alters that schedule with `LoanEvent`s (extra principal, payment holidays, rate changes and recasts)
+//!   and reports the interest saved against the original schedule.
+//! - `financial::naive_date::dated_amortization_schedule()` produces a schedule with payments on calendar dates at a given `Frequency`,
+//!   accruing interest on the days between payments according to a `DayCount` convention. Its cashflows can be fed to `naive_date::xirr`.
+//!
+//! ## Generic Floats
+//!
+//! - `financial::generic` provides `npv`, `fv`, `pv`, `irr`, `mirr`, `xnpv`, `xirr` and `find_root` for any `num_traits::Float`, such as `f32`
+//!   to halve the memory of batch jobs. The `f64` functions at the crate root are thin wrappers over them and return the same results.
+//! - Solvers keep their estimates in `f64` and evaluate NPV in the given type. For types less precise than `f64`, `generic::irr` and `generic::xirr`
+//!   accept a root once NPV is within the rounding error of the cashflows; `generic::irr_with_options` and `generic::xirr_with_options` need a `SolverOptions::f_tolerance` loosened likewise.
+//!
+//! ## Decimal Arithmetic
+//!
+//! - The `decimal` cargo feature adds `financial::decimal`, with `rust_decimal::Decimal` versions of `fv`, `pv`, `pmt`, `npv` and `amortization_schedule`
+//!   computed in exact decimal arithmetic over whole periods, so that results reconcile to the cent instead of carrying floating point noise.
+//! - `decimal::round_bankers()` and `decimal::round_half_up()` round amounts to a currency's minor units, and `decimal::amortization_schedule()`
+//!   rounds every row to them while the balance still closes exactly.
+//! - `decimal::irr()` takes and returns `Decimal`s, but the rate is still solved in `f64`.
 //!
 //! ## Future Work
 //!
-//! - Add More Functions (NPER, PMT, Rate, effect)
+//! - ~~Add More Functions (NPER, PMT, Rate, effect)~~
 //!
 //! ## Testing
 //!
 //! - This crate has over 180 test case, most of them are compared to Excel outputs.
 //! - XIRR is not compared against Excel, since Excel XIRR doesn't always converge to the correct answer and often produce the wrong answer of 0.000000002980.
-//! Instead XIRR are tested by using the XIRR to produce a zero XNPV value.
+//!   Instead XIRR are tested by using the XIRR to produce a zero XNPV value.
 //! - Note that the precision used for equality of floating points is 1e-7
 //!
 //! ## Contribution
@@ -38,17 +122,50 @@
 //! [Github](https://github.com/raymon1/financial)
 //!
 //! <a href="https://www.buymeacoffee.com/raymon1" target="_blank"><img src="https://www.buymeacoffee.com/assets/img/custom_images/orange_img.png" alt="Buy Me A Coffee" style="height: 41px !important;width: 174px !important;box-shadow: 0px 3px 2px 0px rgba(190, 190, 190, 0.5) !important;-webkit-box-shadow: 0px 3px 2px 0px rgba(190, 190, 190, 0.5) !important;" ></a>
-//! 
+//!
 
+mod basis;
+mod calendar;
 mod common;
+mod day_count;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+mod error;
+mod frequency;
+pub mod generic;
 pub mod naive_date;
 mod periodic_cashflow;
 mod scheduled_cashflow;
+mod solver;
 
+pub use crate::basis::Basis;
+pub use crate::calendar::{BusinessDayConvention, Calendar, HolidayCalendar, Weekend};
+pub use crate::day_count::DayCount;
+pub use crate::error::FinancialError;
+pub use crate::frequency::Frequency;
+pub use crate::periodic_cashflow::amortization::{
+    amortization_schedule, AmortizationRow, AmortizationSchedule,
+};
+pub use crate::periodic_cashflow::convert_rate::{convert_rate, Compounding};
+pub use crate::periodic_cashflow::cumipmt::cumipmt;
+pub use crate::periodic_cashflow::cumprinc::cumprinc;
+pub use crate::periodic_cashflow::effect::effect;
 pub use crate::periodic_cashflow::fv::fv;
-pub use crate::periodic_cashflow::irr::irr;
+pub use crate::periodic_cashflow::ipmt::ipmt;
+pub use crate::periodic_cashflow::irr::{irr, irr_with_options, irr_with_report};
+pub use crate::periodic_cashflow::irr_all::{irr_all, IrrRoots};
+pub use crate::periodic_cashflow::loan_schedule::{loan_schedule, LoanEvent, LoanSchedule};
 pub use crate::periodic_cashflow::mirr::mirr;
+pub use crate::periodic_cashflow::nominal::nominal;
+pub use crate::periodic_cashflow::nper::nper;
 pub use crate::periodic_cashflow::npv::npv;
+pub use crate::periodic_cashflow::pmt::pmt;
+pub use crate::periodic_cashflow::ppmt::ppmt;
 pub use crate::periodic_cashflow::pv::pv;
-pub use crate::scheduled_cashflow::xirr::xirr;
-pub use crate::scheduled_cashflow::xnpv::xnpv;
+pub use crate::periodic_cashflow::rate::{rate, rate_with_options};
+pub use crate::scheduled_cashflow::xfv::xfv;
+pub use crate::scheduled_cashflow::xirr::{xirr, xirr_with, xirr_with_options, xirr_with_report};
+pub use crate::scheduled_cashflow::xirr_all::xirr_all;
+pub use crate::scheduled_cashflow::xnpv::{xnpv, xnpv_with};
+pub use crate::scheduled_cashflow::xnpv_at::xnpv_at;
+pub use crate::solver::{SolvePhase, SolveReport, SolverMethod, SolverOptions};
@@ -1,56 +1,308 @@
-use chrono::{DateTime, NaiveDate, Utc};
+mod amortization;
+mod cashflow_schedule;
+mod edate;
+mod eomonth;
+mod networkdays;
+mod schedule;
+mod workday;
+mod yearfrac;
+
+use crate::day_count::DayCount;
+use crate::error::FinancialError;
+pub use crate::naive_date::amortization::{
+    dated_amortization_schedule, DatedAmortizationRow, DatedAmortizationSchedule,
+};
+pub use crate::naive_date::cashflow_schedule::{Cashflow, CashflowSchedule};
+pub use crate::naive_date::edate::edate;
+pub use crate::naive_date::eomonth::eomonth;
+pub use crate::naive_date::networkdays::{networkdays, networkdays_intl};
+pub use crate::naive_date::schedule::{schedule, Schedule, StubRule};
+pub use crate::naive_date::workday::{workday, workday_intl};
+pub use crate::naive_date::yearfrac::yearfrac;
+use crate::periodic_cashflow::irr_all::IrrRoots;
+use crate::solver::{SolveReport, SolverOptions};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 
 fn to_datetime(dates: &[NaiveDate]) -> Vec<DateTime<Utc>> {
     dates
         .iter()
-        .map(|date| DateTime::<Utc>::from_utc(date.and_hms_opt(0, 0, 0).unwrap(), Utc))
+        .map(|date| Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()))
         .collect::<Vec<DateTime<Utc>>>()
 }
 
 /// Returns the internal rate of return for a schedule of cash flows that is not necessarily periodic
 ///
-/// This function is the same as `financial::xirr()` except that it uses `NaiveDate` as the inupt type.
+/// This function is the same as `financial::xirr()` except that it uses `NaiveDate` as the input type.
 ///
 /// # Example
 /// ```
 /// use chrono::{NaiveDate};
 /// let cf = [-379., 100., 100., 100., 100., 100.];
 /// let dates = [
-///     NaiveDate::from_ymd(2016, 7, 8),
-///     NaiveDate::from_ymd(2017, 7, 8),
-///     NaiveDate::from_ymd(2018, 7, 8),
-///     NaiveDate::from_ymd(2019, 7, 8),
-///     NaiveDate::from_ymd(2020, 7, 8),
-///     NaiveDate::from_ymd(2021, 7, 8),
+///     NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2018, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2019, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 7, 8).unwrap(),
 /// ];
 /// assert!((financial::naive_date::xirr(&cf, &dates, None).unwrap() - 0.10004608364).abs() < 1e-7);
 /// ```
-pub fn xirr(values: &[f64], dates: &[NaiveDate], guess: Option<f64>) -> Result<f64, &'static str> {
+pub fn xirr(
+    values: &[f64],
+    dates: &[NaiveDate],
+    guess: Option<f64>,
+) -> Result<f64, FinancialError> {
     crate::scheduled_cashflow::xirr::xirr(values, &to_datetime(dates), guess)
 }
 
+/// Returns the internal rate of return like `xirr`, with the solver tuned by `options`.
+///
+/// This function is the same as `financial::xirr_with_options()` except that it uses `NaiveDate` as the input type.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// let cf = [-379., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2018, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2019, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 7, 8).unwrap(),
+/// ];
+/// let options = financial::SolverOptions {
+///     f_tolerance: 1e-10,
+///     ..Default::default()
+/// };
+/// let rate = financial::naive_date::xirr_with_options(&cf, &dates, None, &options).unwrap();
+/// assert!(financial::naive_date::xnpv(rate, &cf, &dates).unwrap().abs() <= 1e-10);
+/// ```
+pub fn xirr_with_options(
+    values: &[f64],
+    dates: &[NaiveDate],
+    guess: Option<f64>,
+    options: &SolverOptions,
+) -> Result<f64, FinancialError> {
+    crate::scheduled_cashflow::xirr::xirr_with_options(values, &to_datetime(dates), guess, options)
+}
+
+/// Runs the solver of `xirr_with_options`, and reports how it went.
+///
+/// This function is the same as `financial::xirr_with_report()` except that it uses `NaiveDate` as the input type.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// let cf = [-379., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2018, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2019, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 7, 8).unwrap(),
+/// ];
+/// let options = financial::SolverOptions::default();
+/// let report = financial::naive_date::xirr_with_report(&cf, &dates, None, &options, true).unwrap();
+/// assert_eq!(report.phase, financial::SolvePhase::Newton);
+/// assert!((report.root.unwrap() - 0.10004608364).abs() < 1e-7);
+/// ```
+pub fn xirr_with_report(
+    values: &[f64],
+    dates: &[NaiveDate],
+    guess: Option<f64>,
+    options: &SolverOptions,
+    trace: bool,
+) -> Result<SolveReport, FinancialError> {
+    crate::scheduled_cashflow::xirr::xirr_with_report(
+        values,
+        &to_datetime(dates),
+        guess,
+        options,
+        trace,
+    )
+}
+
+/// Returns all the internal rates of return within `range` for a schedule of cash flows that is not necessarily periodic.
+///
+/// This function is the same as `financial::xirr_all()` except that it uses `NaiveDate` as the input type.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// let cf = [-1000., 3600., -4310., 1716.];
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2015, 1, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2016, 1, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2016, 12, 31).unwrap(),
+///     NaiveDate::from_ymd_opt(2017, 12, 31).unwrap(),
+/// ];
+/// let irr = financial::naive_date::xirr_all(&cf, &dates, (-0.99, 1.)).unwrap();
+/// assert_eq!(irr.roots.len(), 3);
+/// assert!(!irr.single_sign_change);
+/// ```
+pub fn xirr_all(
+    values: &[f64],
+    dates: &[NaiveDate],
+    range: (f64, f64),
+) -> Result<IrrRoots, FinancialError> {
+    crate::scheduled_cashflow::xirr_all::xirr_all(values, &to_datetime(dates), range)
+}
+
 /// Returns the net present value for a schedule of cash flows that is not necessarily periodic.
 ///
-/// This function is the same as `financial::xirr()` except that it uses `NaiveDate` as the inupt type.
+/// This function is the same as `financial::xirr()` except that it uses `NaiveDate` as the input type.
 ///
 /// # Example
 /// ```
-/// use chrono::{NaiveDate, DateTime, Utc, Duration};
+/// use chrono::NaiveDate;
 /// let cf = [-500., 100., 100., 100., 100., 100.];
 /// let dates = [
-///     NaiveDate::from_ymd(2016, 7, 8),
-///     NaiveDate::from_ymd(2017, 7, 8),
-///     NaiveDate::from_ymd(2018, 7, 8),
-///     NaiveDate::from_ymd(2019, 7, 8),
-///     NaiveDate::from_ymd(2020, 7, 8),
-///     NaiveDate::from_ymd(2021, 7, 8),
+///     NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2018, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2019, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 7, 8).unwrap(),
 /// ];
 /// assert_eq!(financial::naive_date::xnpv(0.1, &cf, &dates).unwrap(), -120.9553674519204);
 /// ```
-pub fn xnpv(rate: f64, values: &[f64], dates: &[NaiveDate]) -> Result<f64, &'static str> {
+pub fn xnpv(rate: f64, values: &[f64], dates: &[NaiveDate]) -> Result<f64, FinancialError> {
     crate::scheduled_cashflow::xnpv::xnpv(rate, values, &to_datetime(dates))
 }
 
+/// Returns the value at `valuation_date` of a schedule of cash flows that is not necessarily periodic.
+///
+/// This function is the same as `financial::xnpv_at()` except that it uses `NaiveDate` as the input type.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// let cf = [-500., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2018, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2019, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 7, 8).unwrap(),
+/// ];
+/// let reporting_date = NaiveDate::from_ymd_opt(2018, 12, 31).unwrap();
+///
+/// let xnpv = financial::naive_date::xnpv(0.1, &cf, &dates).unwrap();
+/// let xnpv_at = financial::naive_date::xnpv_at(0.1, &cf, &dates, reporting_date).unwrap();
+/// assert!((xnpv_at - xnpv * 1.1_f64.powf(906. / 365.)).abs() < 1e-9);
+/// ```
+pub fn xnpv_at(
+    rate: f64,
+    values: &[f64],
+    dates: &[NaiveDate],
+    valuation_date: NaiveDate,
+) -> Result<f64, FinancialError> {
+    crate::scheduled_cashflow::xnpv_at::xnpv_at(
+        rate,
+        values,
+        &to_datetime(dates),
+        to_datetime(&[valuation_date])[0],
+    )
+}
+
+/// Returns the future value at `horizon_date` of a schedule of cash flows that is not necessarily periodic.
+///
+/// This function is the same as `financial::xfv()` except that it uses `NaiveDate` as the input type.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// let cf = [-500., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2018, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2019, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 7, 8).unwrap(),
+/// ];
+///
+/// let xnpv = financial::naive_date::xnpv(0.1, &cf, &dates).unwrap();
+/// let xfv = financial::naive_date::xfv(0.1, &cf, &dates, dates[5]).unwrap();
+/// assert!((xfv - xnpv * 1.1_f64.powf(1826. / 365.)).abs() < 1e-9);
+/// ```
+pub fn xfv(
+    rate: f64,
+    values: &[f64],
+    dates: &[NaiveDate],
+    horizon_date: NaiveDate,
+) -> Result<f64, FinancialError> {
+    crate::scheduled_cashflow::xfv::xfv(
+        rate,
+        values,
+        &to_datetime(dates),
+        to_datetime(&[horizon_date])[0],
+    )
+}
+
+/// Returns the net present value for a schedule of cash flows that is not necessarily periodic, according to `day_count`.
+///
+/// This function is the same as `financial::xnpv_with()` except that it uses `NaiveDate` as the input type.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use financial::DayCount;
+/// let cf = [-500., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2018, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2019, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 7, 8).unwrap(),
+/// ];
+/// let npv = financial::npv(0.1, &cf[1..]) + cf[0];
+/// let xnpv = financial::naive_date::xnpv_with(0.1, &cf, &dates, &DayCount::Thirty360).unwrap();
+/// assert!((xnpv - npv).abs() < 1e-9);
+/// ```
+pub fn xnpv_with(
+    rate: f64,
+    values: &[f64],
+    dates: &[NaiveDate],
+    day_count: &DayCount,
+) -> Result<f64, FinancialError> {
+    crate::scheduled_cashflow::xnpv::xnpv_with(rate, values, &to_datetime(dates), day_count)
+}
+
+/// Returns the internal rate of return for a schedule of cash flows that is not necessarily periodic, according to `day_count`.
+///
+/// This function is the same as `financial::xirr_with()` except that it uses `NaiveDate` as the input type.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use financial::DayCount;
+/// let cf = [-379., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2018, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2019, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 7, 8).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 7, 8).unwrap(),
+/// ];
+/// let xirr = financial::naive_date::xirr_with(&cf, &dates, None, &DayCount::Thirty360).unwrap();
+/// assert!((xirr - financial::irr(&cf, None).unwrap()).abs() < 1e-7);
+/// ```
+pub fn xirr_with(
+    values: &[f64],
+    dates: &[NaiveDate],
+    guess: Option<f64>,
+    day_count: &DayCount,
+) -> Result<f64, FinancialError> {
+    crate::scheduled_cashflow::xirr::xirr_with(values, &to_datetime(dates), guess, day_count)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,16 +310,14 @@ mod tests {
     #[test]
     fn xnpv_with_zero_rate() {
         let cf: [f64; 10000] = [100.; 10000];
-        let somedate: NaiveDate = NaiveDate::from_ymd(2021, 1, 1);
+        let somedate: NaiveDate = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
         let dates0: [NaiveDate; 10000] = [somedate; 10000];
         let mut dates: [NaiveDate; 10000] = [somedate; 10000];
 
-        let mut i = 0;
-        for d in dates0.iter() {
+        for (i, d) in dates0.iter().enumerate() {
             dates[i] = d
                 .checked_add_signed(Duration::weeks(52 * (i as i64)))
                 .unwrap();
-            i = i + 1;
         }
 
         assert_eq!(xnpv(0., &cf, &dates).unwrap(), cf.iter().sum());
@@ -77,13 +327,47 @@ mod tests {
     fn xnpv_test() {
         let cf = [-500., 100., 100., 100., 100., 100.];
         let dates = [
-            NaiveDate::from_ymd(2016, 7, 8),
-            NaiveDate::from_ymd(2017, 7, 8),
-            NaiveDate::from_ymd(2018, 7, 8),
-            NaiveDate::from_ymd(2019, 7, 8),
-            NaiveDate::from_ymd(2020, 7, 8),
-            NaiveDate::from_ymd(2021, 7, 8),
+            NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+            NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+            NaiveDate::from_ymd_opt(2018, 7, 8).unwrap(),
+            NaiveDate::from_ymd_opt(2019, 7, 8).unwrap(),
+            NaiveDate::from_ymd_opt(2020, 7, 8).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 7, 8).unwrap(),
         ];
         assert_eq!(xnpv(0.1, &cf, &dates).unwrap(), -120.9553674519204);
     }
+
+    #[test]
+    fn naive_date_functions_fail_on_invalid_values() {
+        let dates = [
+            NaiveDate::from_ymd_opt(2016, 7, 8).unwrap(),
+            NaiveDate::from_ymd_opt(2017, 7, 8).unwrap(),
+        ];
+
+        assert!(xnpv(0.1, &[], &[]).is_err());
+        assert!(xirr(&[], &[], None).is_err());
+        assert!(xnpv(0.1, &[-100., f64::NAN], &dates).is_err());
+        assert!(xirr(&[-100., f64::INFINITY], &dates, None).is_err());
+        assert!(xirr(&[0., 0.], &dates, None).is_err());
+    }
+
+    #[test]
+    fn xirr_with_bus_252_zeroes_xnpv_with() {
+        let calendar = crate::calendar::HolidayCalendar::new(&[
+            NaiveDate::from_ymd_opt(2021, 4, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 12, 24).unwrap(),
+        ]);
+        let day_count = DayCount::Bus252(std::sync::Arc::new(calendar));
+        let cf = [-1000., 30., 30., 1030.];
+        let dates = [
+            NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 4, 5).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 10, 4).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 1, 3).unwrap(),
+        ];
+
+        let rate = xirr_with(&cf, &dates, None, &day_count).unwrap();
+        assert!(xnpv_with(rate, &cf, &dates, &day_count).unwrap().abs() < 1e-7);
+        assert_ne!(rate, xirr(&cf, &dates, None).unwrap());
+    }
 }
@@ -0,0 +1,253 @@
+use crate::common::utils;
+use crate::day_count::DayCount;
+use crate::error::FinancialError;
+use crate::frequency::Frequency;
+use crate::periodic_cashflow::pmt::pmt;
+use chrono::NaiveDate;
+use std::convert::TryFrom;
+
+/// A single payment of a dated amortization schedule.
+///
+/// Amounts follow the same sign convention as `financial::amortization_schedule()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatedAmortizationRow {
+    /// The date the payment is due.
+    pub date: NaiveDate,
+    /// The payment made on `date`.
+    pub payment: f64,
+    /// The interest accrued since the previous payment, measured with the day count convention.
+    pub interest: f64,
+    /// The part of the payment that repays the loan.
+    pub principal: f64,
+    /// The balance outstanding after this payment.
+    pub balance: f64,
+}
+
+/// A loan disbursed at `start_date` and repaid by the dated payments in `rows`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatedAmortizationSchedule {
+    /// The date the loan is disbursed.
+    pub start_date: NaiveDate,
+    /// The amount of the loan.
+    pub pv: f64,
+    /// The payments, in date order.
+    pub rows: Vec<DatedAmortizationRow>,
+}
+
+impl DatedAmortizationSchedule {
+    /// Returns the sum of all payments.
+    pub fn total_payment(&self) -> f64 {
+        self.rows.iter().map(|row| row.payment).sum()
+    }
+
+    /// Returns the sum of the interest paid over the whole loan.
+    pub fn total_interest(&self) -> f64 {
+        self.rows.iter().map(|row| row.interest).sum()
+    }
+
+    /// Returns the sum of the principal repaid over the whole loan.
+    pub fn total_principal(&self) -> f64 {
+        self.rows.iter().map(|row| row.principal).sum()
+    }
+
+    /// Returns the values and dates of the loan cashflows, starting with the disbursement of `pv`.
+    pub fn cashflows(&self) -> (Vec<f64>, Vec<NaiveDate>) {
+        let values = std::iter::once(self.pv)
+            .chain(self.rows.iter().map(|row| row.payment))
+            .collect();
+        let dates = std::iter::once(self.start_date)
+            .chain(self.rows.iter().map(|row| row.date))
+            .collect();
+
+        (values, dates)
+    }
+
+    /// Returns the annual yield of the loan, i.e. the XIRR of its cashflows.
+    pub fn yield_rate(&self) -> Result<f64, FinancialError> {
+        let (values, dates) = self.cashflows();
+        super::xirr(&values, &dates, None)
+    }
+}
+
+/// Returns the amortization schedule of a level-payment loan with payments on calendar dates.
+///
+/// The level payment is calculated from `annual_rate` divided by the number of periods per year,
+/// while the interest of each period accrues on the days between payments according to `day_count`.
+/// The last payment is adjusted so the balance closes exactly at zero.
+/// When `decimals` is provided, payments and interest are rounded to that many decimal places.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use financial::{DayCount, Frequency};
+///
+/// let start = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+/// let schedule = financial::naive_date::dated_amortization_schedule(
+///     0.12, 12, 1000., start, Frequency::Monthly, DayCount::Act365Fixed, Some(2),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(schedule.rows[0].date, NaiveDate::from_ymd_opt(2021, 2, 28).unwrap());
+/// assert_eq!(schedule.rows[11].balance, 0.);
+///
+/// let apr = schedule.yield_rate().unwrap();
+/// ```
+pub fn dated_amortization_schedule(
+    annual_rate: f64,
+    nper: u32,
+    pv: f64,
+    start_date: NaiveDate,
+    frequency: Frequency,
+    day_count: DayCount,
+    decimals: Option<u32>,
+) -> Result<DatedAmortizationSchedule, FinancialError> {
+    if nper == 0 {
+        return Err(FinancialError::InvalidArgument {
+            name: "nper",
+            reason: "must be greater than zero",
+        });
+    }
+    if !annual_rate.is_finite() || annual_rate <= -1. {
+        return Err(FinancialError::InvalidArgument {
+            name: "annual_rate",
+            reason: "must be finite and greater than -1",
+        });
+    }
+
+    let round = |x: f64| utils::round_to_decimals(x, decimals);
+    let periodic_rate = annual_rate / frequency.periods_per_year() as f64;
+    let payment = round(pmt(periodic_rate, nper as f64, Some(pv), None, None));
+
+    let mut balance = pv;
+    let mut previous_date = start_date;
+    let mut rows = Vec::with_capacity(nper as usize);
+
+    for period in 1..=nper {
+        let date = i32::try_from(period)
+            .ok()
+            .and_then(|period| frequency.advance(start_date, period))
+            .ok_or(FinancialError::InvalidArgument {
+                name: "nper",
+                reason: "payment dates are out of range",
+            })?;
+
+        let interest = round(-balance * annual_rate * day_count.year_fraction(previous_date, date));
+        let principal = if period == nper {
+            -balance
+        } else {
+            payment - interest
+        };
+        balance = if period == nper {
+            0.
+        } else {
+            balance + principal
+        };
+
+        rows.push(DatedAmortizationRow {
+            date,
+            payment: interest + principal,
+            interest,
+            principal,
+            balance,
+        });
+        previous_date = date;
+    }
+
+    Ok(DatedAmortizationSchedule {
+        start_date,
+        pv,
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+    use crate::periodic_cashflow::amortization::amortization_schedule;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn dated_amortization_schedule_with_thirty_360_matches_periodic_schedule() {
+        let dated = dated_amortization_schedule(
+            0.12,
+            24,
+            5000.,
+            date(2021, 1, 15),
+            Frequency::Monthly,
+            DayCount::Thirty360,
+            None,
+        )
+        .unwrap();
+        let periodic = amortization_schedule(0.01, 24, 5000., None, None, None).unwrap();
+
+        for (d, p) in dated.rows.iter().zip(periodic.rows.iter()) {
+            assert!((d.interest - p.interest).abs() <= PRECISION, "{:?}", d);
+            assert!((d.balance - p.balance).abs() <= PRECISION, "{:?}", d);
+        }
+    }
+
+    #[test]
+    fn dated_amortization_schedule_accrues_actual_days() {
+        let schedule = dated_amortization_schedule(
+            0.12,
+            12,
+            1000.,
+            date(2021, 1, 1),
+            Frequency::Monthly,
+            DayCount::Act360,
+            None,
+        )
+        .unwrap();
+
+        assert!((schedule.rows[0].interest - -1000. * 0.12 * 31. / 360.).abs() <= PRECISION);
+        assert_eq!(schedule.rows[11].date, date(2022, 1, 1));
+        assert_eq!(schedule.rows[11].balance, 0.);
+        assert!((schedule.total_principal() - -1000.).abs() <= PRECISION);
+    }
+
+    #[test]
+    fn dated_amortization_schedule_yield_matches_rate() {
+        let schedule = dated_amortization_schedule(
+            0.1,
+            4,
+            1000.,
+            date(2021, 1, 1),
+            Frequency::Annually,
+            DayCount::Act365Fixed,
+            None,
+        )
+        .unwrap();
+
+        // 2024 is a leap year, the Act/365 interest accrues a day more than XIRR discounts
+        let yield_rate = schedule.yield_rate().unwrap();
+        assert!((yield_rate - 0.1).abs() <= 1e-3, "got {}", yield_rate);
+
+        let (values, dates) = schedule.cashflows();
+        assert_eq!(values[0], 1000.);
+        assert_eq!(dates[0], date(2021, 1, 1));
+        assert_eq!(values.len(), 5);
+    }
+
+    #[test]
+    fn dated_amortization_schedule_rejects_invalid_arguments() {
+        let schedule = |annual_rate, nper| {
+            dated_amortization_schedule(
+                annual_rate,
+                nper,
+                1000.,
+                date(2021, 1, 1),
+                Frequency::Annually,
+                DayCount::Act365Fixed,
+                None,
+            )
+        };
+
+        assert!(schedule(0.1, 0).is_err());
+        assert!(schedule(f64::NAN, 12).is_err());
+        assert!(schedule(-5., 12).is_err());
+    }
+}
@@ -0,0 +1,372 @@
+use crate::error::FinancialError;
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
+use std::iter::FromIterator;
+
+/// An amount paid or received on a date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cashflow {
+    /// The date the amount is paid or received on.
+    pub date: NaiveDate,
+    /// Negative when paid, positive when received.
+    pub amount: f64,
+}
+
+/// An owned schedule of cash flows that is not necessarily periodic, to use instead of parallel slices of
+/// values and dates.
+///
+/// Cash flows are kept in the order they're given, like the slices of `financial::naive_date::xnpv()`,
+/// until they're sorted or merged.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use financial::naive_date::CashflowSchedule;
+///
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// let cf: CashflowSchedule = vec![
+///     (date(2016, 7, 8), -500.),
+///     (date(2017, 7, 8), 100.),
+///     (date(2018, 7, 8), 100.),
+///     (date(2019, 7, 8), 100.),
+///     (date(2020, 7, 8), 100.),
+///     (date(2021, 7, 8), 100.),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(cf.total(), 0.);
+/// assert_eq!(cf.xnpv(0.1).unwrap(), -120.9553674519204);
+/// assert_eq!(cf.xirr(None).unwrap(), 0.);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CashflowSchedule {
+    cashflows: Vec<Cashflow>,
+}
+
+impl CashflowSchedule {
+    /// Returns the schedule of the parallel slices `values` and `dates`.
+    pub fn new(values: &[f64], dates: &[NaiveDate]) -> Result<CashflowSchedule, FinancialError> {
+        if values.len() != dates.len() {
+            return Err(FinancialError::LengthMismatch {
+                values: values.len(),
+                dates: dates.len(),
+            });
+        }
+
+        Ok(dates.iter().copied().zip(values.iter().copied()).collect())
+    }
+
+    /// Returns the schedule of the parallel slices `values` and `dates` sorted by date, such as a ledger export
+    /// in arbitrary order, with the amounts on the same date summed when `aggregate_same_date` is set.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use financial::naive_date::CashflowSchedule;
+    ///
+    /// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    /// let values = [60., -100., 50.];
+    /// let dates = [date(2021, 7, 1), date(2021, 1, 1), date(2021, 7, 1)];
+    ///
+    /// let cf = CashflowSchedule::normalized(&values, &dates, true).unwrap();
+    /// assert_eq!(cf.values(), vec![-100., 110.]);
+    /// assert!(financial::naive_date::xirr(&values, &dates, None).is_err());
+    /// assert!(cf.xirr(None).is_ok());
+    /// ```
+    pub fn normalized(
+        values: &[f64],
+        dates: &[NaiveDate],
+        aggregate_same_date: bool,
+    ) -> Result<CashflowSchedule, FinancialError> {
+        let mut schedule = CashflowSchedule::new(values, dates)?;
+        if aggregate_same_date {
+            schedule.merge_same_day();
+        } else {
+            schedule.sort();
+        }
+
+        Ok(schedule)
+    }
+
+    /// Appends a cash flow, after the ones already in the schedule whatever its date.
+    pub fn push(&mut self, date: NaiveDate, amount: f64) {
+        self.cashflows.push(Cashflow { date, amount });
+    }
+
+    /// Returns the cash flows in their current order.
+    pub fn cashflows(&self) -> &[Cashflow] {
+        &self.cashflows
+    }
+
+    /// Returns the amounts, in the order of the cash flows, as expected by `financial::naive_date::xnpv()`.
+    pub fn values(&self) -> Vec<f64> {
+        self.cashflows.iter().map(|cf| cf.amount).collect()
+    }
+
+    /// Returns the dates, in the order of the cash flows, as expected by `financial::naive_date::xnpv()`.
+    pub fn dates(&self) -> Vec<NaiveDate> {
+        self.cashflows.iter().map(|cf| cf.date).collect()
+    }
+
+    /// Returns the number of cash flows, including those on the same date.
+    pub fn len(&self) -> usize {
+        self.cashflows.len()
+    }
+
+    /// Returns whether the schedule has no cash flow.
+    pub fn is_empty(&self) -> bool {
+        self.cashflows.is_empty()
+    }
+
+    /// Sorts the cash flows by date, keeping the order of cash flows on the same date.
+    pub fn sort(&mut self) {
+        self.cashflows.sort_by_key(|cf| cf.date);
+    }
+
+    /// Sorts the cash flows by date and sums the amounts of cash flows on the same date.
+    pub fn merge_same_day(&mut self) {
+        self.sort();
+
+        let mut merged: Vec<Cashflow> = Vec::with_capacity(self.cashflows.len());
+        for cf in self.cashflows.drain(..) {
+            match merged.last_mut() {
+                Some(last) if last.date == cf.date => last.amount += cf.amount,
+                _ => merged.push(cf),
+            }
+        }
+
+        self.cashflows = merged;
+    }
+
+    /// Returns the sum of the amounts, undiscounted.
+    pub fn total(&self) -> f64 {
+        self.cashflows.iter().map(|cf| cf.amount).sum()
+    }
+
+    /// Splits the schedule into the cash flows before `date` and the cash flows on or after it.
+    pub fn split_at(&self, date: NaiveDate) -> (CashflowSchedule, CashflowSchedule) {
+        let (before, after) = self.cashflows.iter().partition(|cf| cf.date < date);
+
+        (
+            CashflowSchedule { cashflows: before },
+            CashflowSchedule { cashflows: after },
+        )
+    }
+
+    /// Returns the net present value of the schedule, see `financial::naive_date::xnpv()`.
+    pub fn xnpv(&self, rate: f64) -> Result<f64, FinancialError> {
+        crate::naive_date::xnpv(rate, &self.values(), &self.dates())
+    }
+
+    /// Returns the value of the schedule at `valuation_date`, whatever the order of the cash flows,
+    /// see `financial::naive_date::xnpv_at()`.
+    ///
+    /// This is `xnpv` when `valuation_date` is the earliest date of the schedule.
+    pub fn xnpv_at(&self, rate: f64, valuation_date: NaiveDate) -> Result<f64, FinancialError> {
+        crate::naive_date::xnpv_at(rate, &self.values(), &self.dates(), valuation_date)
+    }
+
+    /// Returns the future value of the schedule at `horizon_date`, see `financial::naive_date::xfv()`.
+    pub fn xfv(&self, rate: f64, horizon_date: NaiveDate) -> Result<f64, FinancialError> {
+        crate::naive_date::xfv(rate, &self.values(), &self.dates(), horizon_date)
+    }
+
+    /// Returns the internal rate of return of the schedule, see `financial::naive_date::xirr()`.
+    pub fn xirr(&self, guess: Option<f64>) -> Result<f64, FinancialError> {
+        crate::naive_date::xirr(&self.values(), &self.dates(), guess)
+    }
+}
+
+impl FromIterator<(NaiveDate, f64)> for CashflowSchedule {
+    fn from_iter<I: IntoIterator<Item = (NaiveDate, f64)>>(iter: I) -> CashflowSchedule {
+        CashflowSchedule {
+            cashflows: iter
+                .into_iter()
+                .map(|(date, amount)| Cashflow { date, amount })
+                .collect(),
+        }
+    }
+}
+
+impl FromIterator<Cashflow> for CashflowSchedule {
+    fn from_iter<I: IntoIterator<Item = Cashflow>>(iter: I) -> CashflowSchedule {
+        CashflowSchedule {
+            cashflows: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl From<BTreeMap<NaiveDate, f64>> for CashflowSchedule {
+    fn from(map: BTreeMap<NaiveDate, f64>) -> CashflowSchedule {
+        map.into_iter().collect()
+    }
+}
+
+/// The cash flows are sorted by date, since maps iterate in arbitrary order.
+impl From<HashMap<NaiveDate, f64>> for CashflowSchedule {
+    fn from(map: HashMap<NaiveDate, f64>) -> CashflowSchedule {
+        let mut schedule: CashflowSchedule = map.into_iter().collect();
+        schedule.sort();
+        schedule
+    }
+}
+
+impl IntoIterator for CashflowSchedule {
+    type Item = Cashflow;
+    type IntoIter = std::vec::IntoIter<Cashflow>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cashflows.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn constructors_agree() {
+        let values = [-100., 60., 60.];
+        let dates = [date(2021, 1, 1), date(2021, 7, 1), date(2022, 1, 1)];
+
+        let from_slices = CashflowSchedule::new(&values, &dates).unwrap();
+        let from_tuples: CashflowSchedule =
+            dates.iter().copied().zip(values.iter().copied()).collect();
+        let from_btree_map = CashflowSchedule::from(
+            dates
+                .iter()
+                .copied()
+                .zip(values.iter().copied())
+                .collect::<BTreeMap<NaiveDate, f64>>(),
+        );
+        let from_hash_map = CashflowSchedule::from(
+            dates
+                .iter()
+                .copied()
+                .zip(values.iter().copied())
+                .collect::<HashMap<NaiveDate, f64>>(),
+        );
+
+        assert_eq!(from_slices.values(), values.to_vec());
+        assert_eq!(from_slices.dates(), dates.to_vec());
+        assert_eq!(from_slices, from_tuples);
+        assert_eq!(from_slices, from_btree_map);
+        assert_eq!(from_slices, from_hash_map);
+
+        assert_eq!(
+            CashflowSchedule::new(&values[1..], &dates),
+            Err(FinancialError::LengthMismatch {
+                values: 2,
+                dates: 3
+            })
+        );
+    }
+
+    #[test]
+    fn merge_same_day_sorts_and_sums() {
+        let mut cf = CashflowSchedule::default();
+        cf.push(date(2021, 7, 1), 60.);
+        cf.push(date(2021, 1, 1), -100.);
+        cf.push(date(2021, 7, 1), 50.);
+
+        let mut sorted = cf.clone();
+        sorted.sort();
+        assert_eq!(sorted.values(), vec![-100., 60., 50.]);
+
+        cf.merge_same_day();
+        assert_eq!(cf.len(), 2);
+        assert_eq!(cf.dates(), vec![date(2021, 1, 1), date(2021, 7, 1)]);
+        assert_eq!(cf.values(), vec![-100., 110.]);
+        assert_eq!(cf.total(), 10.);
+    }
+
+    #[test]
+    fn normalized_sorts_and_optionally_aggregates() {
+        let values = [60., -100., 50.];
+        let dates = [date(2021, 7, 1), date(2021, 1, 1), date(2021, 7, 1)];
+
+        let sorted = CashflowSchedule::normalized(&values, &dates, false).unwrap();
+        assert_eq!(sorted.values(), vec![-100., 60., 50.]);
+        assert_eq!(
+            sorted.dates(),
+            vec![date(2021, 1, 1), date(2021, 7, 1), date(2021, 7, 1)]
+        );
+
+        let aggregated = CashflowSchedule::normalized(&values, &dates, true).unwrap();
+        assert_eq!(aggregated.values(), vec![-100., 110.]);
+        assert!((aggregated.xirr(None).unwrap() - sorted.xirr(None).unwrap()).abs() < PRECISION);
+
+        assert!(CashflowSchedule::normalized(&values[1..], &dates, true).is_err());
+    }
+
+    #[test]
+    fn xnpv_at_uses_valuation_date() {
+        let cf = CashflowSchedule::new(
+            &[60., -100., 60.],
+            &[date(2021, 7, 1), date(2021, 1, 1), date(2022, 1, 1)],
+        )
+        .unwrap();
+        let mut sorted = cf.clone();
+        sorted.sort();
+
+        // the earliest date gives xnpv, whatever the order
+        assert!(
+            (cf.xnpv_at(0.1, date(2021, 1, 1)).unwrap() - sorted.xnpv(0.1).unwrap()).abs()
+                < PRECISION
+        );
+        assert!(cf.xnpv(0.1).is_err());
+
+        // a later valuation date compounds the value at the earliest date
+        let later = cf.xnpv_at(0.1, date(2021, 3, 1)).unwrap();
+        assert!((later - sorted.xnpv(0.1).unwrap() * 1.1_f64.powf(59. / 365.)).abs() < PRECISION);
+
+        assert_eq!(
+            cf.xfv(0.1, date(2022, 1, 1)).unwrap(),
+            cf.xnpv_at(0.1, date(2022, 1, 1)).unwrap()
+        );
+        assert!(cf.xnpv_at(-1., date(2021, 1, 1)).is_err());
+        assert!(CashflowSchedule::default()
+            .xnpv_at(0.1, date(2021, 1, 1))
+            .is_err());
+    }
+
+    #[test]
+    fn split_at_works() {
+        let cf = CashflowSchedule::new(
+            &[-100., 60., 60.],
+            &[date(2021, 1, 1), date(2021, 7, 1), date(2022, 1, 1)],
+        )
+        .unwrap();
+
+        let (before, after) = cf.split_at(date(2021, 7, 1));
+        assert_eq!(before.dates(), vec![date(2021, 1, 1)]);
+        assert_eq!(after.dates(), vec![date(2021, 7, 1), date(2022, 1, 1)]);
+
+        let (before, after) = cf.split_at(date(2020, 1, 1));
+        assert!(before.is_empty());
+        assert_eq!(after, cf);
+    }
+
+    #[test]
+    fn xnpv_and_xirr_match_functions() {
+        let values = [-100., 60., 60.];
+        let dates = [date(2021, 1, 1), date(2021, 7, 1), date(2022, 1, 1)];
+        let cf = CashflowSchedule::new(&values, &dates).unwrap();
+
+        assert_eq!(
+            cf.xnpv(0.1).unwrap(),
+            crate::naive_date::xnpv(0.1, &values, &dates).unwrap()
+        );
+        assert_eq!(
+            cf.xirr(None).unwrap(),
+            crate::naive_date::xirr(&values, &dates, None).unwrap()
+        );
+        assert!(CashflowSchedule::default().xnpv(0.1).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use crate::common::utils;
+use crate::error::FinancialError;
+use chrono::NaiveDate;
+
+/// Returns the date `months` before or after `start_date`, like Excel's `EDATE`.
+///
+/// The day of month is kept, clamped to the end of shorter months.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+/// assert_eq!(
+///     financial::naive_date::edate(start, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 2, 28).unwrap()
+/// );
+/// ```
+pub fn edate(start_date: NaiveDate, months: i32) -> Result<NaiveDate, FinancialError> {
+    utils::add_months(start_date, months).ok_or(FinancialError::InvalidArgument {
+        name: "months",
+        reason: "must keep the date within the range of NaiveDate",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn edate_clamps_to_end_of_month() {
+        assert_eq!(edate(date(2020, 3, 31), -1).unwrap(), date(2020, 2, 29));
+        assert_eq!(edate(date(2020, 2, 29), 12).unwrap(), date(2021, 2, 28));
+        assert_eq!(edate(date(2021, 2, 28), 1).unwrap(), date(2021, 3, 28));
+        assert_eq!(edate(date(2021, 11, 15), -23).unwrap(), date(2019, 12, 15));
+        assert_eq!(edate(date(2021, 11, 15), 0).unwrap(), date(2021, 11, 15));
+    }
+
+    #[test]
+    fn edate_fails_out_of_range() {
+        assert!(edate(date(2021, 1, 1), i32::MAX).is_err());
+        assert!(edate(NaiveDate::MAX, 1).is_err());
+    }
+}
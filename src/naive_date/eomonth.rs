@@ -0,0 +1,46 @@
+use crate::common::utils;
+use crate::error::FinancialError;
+use chrono::{Datelike, NaiveDate};
+
+/// Returns the last day of the month `months` before or after `start_date`, like Excel's `EOMONTH`.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2021, 1, 15).unwrap();
+/// assert_eq!(
+///     financial::naive_date::eomonth(start, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 2, 28).unwrap()
+/// );
+/// ```
+pub fn eomonth(start_date: NaiveDate, months: i32) -> Result<NaiveDate, FinancialError> {
+    let first_of_month = start_date.with_day(1).unwrap();
+
+    utils::add_months(first_of_month, months)
+        .and_then(|month| utils::add_months(month, 1))
+        .and_then(|next_month| next_month.pred_opt())
+        .ok_or(FinancialError::InvalidArgument {
+            name: "months",
+            reason: "must keep the date within the range of NaiveDate",
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn eomonth_works() {
+        assert_eq!(eomonth(date(2020, 1, 31), 1).unwrap(), date(2020, 2, 29));
+        assert_eq!(eomonth(date(2021, 3, 1), -1).unwrap(), date(2021, 2, 28));
+        assert_eq!(eomonth(date(2021, 12, 5), 0).unwrap(), date(2021, 12, 31));
+        assert_eq!(eomonth(date(2021, 12, 5), 13).unwrap(), date(2023, 1, 31));
+        assert!(eomonth(NaiveDate::MAX, 0).is_err());
+        assert!(eomonth(date(2021, 1, 1), i32::MIN).is_err());
+    }
+}
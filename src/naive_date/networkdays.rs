@@ -0,0 +1,95 @@
+use crate::calendar::{Calendar, HolidayCalendar, Weekend};
+use chrono::NaiveDate;
+
+/// Returns the number of business days from `start_date` to `end_date`, both included, skipping Saturdays,
+/// Sundays and `holidays`, like Excel's `NETWORKDAYS`.
+///
+/// The result is negative when `end_date` is before `start_date`.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2012, 10, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2013, 3, 1).unwrap();
+/// let holidays = [NaiveDate::from_ymd_opt(2012, 11, 22).unwrap()];
+/// assert_eq!(financial::naive_date::networkdays(start, end, None), 110);
+/// assert_eq!(financial::naive_date::networkdays(start, end, Some(&holidays)), 109);
+/// ```
+pub fn networkdays(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    holidays: Option<&[NaiveDate]>,
+) -> i64 {
+    networkdays_intl(start_date, end_date, None, holidays)
+}
+
+/// Returns the number of business days from `start_date` to `end_date`, both included, skipping the days
+/// of `weekend` (Saturday and Sunday by default) and `holidays`, like Excel's `NETWORKDAYS.INTL`.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2006, 1, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2006, 2, 1).unwrap();
+/// let holidays = [
+///     NaiveDate::from_ymd_opt(2006, 1, 2).unwrap(),
+///     NaiveDate::from_ymd_opt(2006, 1, 16).unwrap(),
+/// ];
+/// let weekend = "0010001".parse().ok();
+/// assert_eq!(financial::naive_date::networkdays_intl(start, end, weekend, Some(&holidays)), 20);
+/// ```
+pub fn networkdays_intl(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    weekend: Option<Weekend>,
+    holidays: Option<&[NaiveDate]>,
+) -> i64 {
+    if end_date < start_date {
+        return -networkdays_intl(end_date, start_date, weekend, holidays);
+    }
+
+    let calendar = HolidayCalendar::new(holidays.unwrap_or_default())
+        .with_weekend(weekend.unwrap_or_default());
+
+    calendar.business_days_between(start_date, end_date) + calendar.is_business_day(end_date) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn networkdays_works() {
+        let holidays = [date(2012, 11, 22), date(2012, 12, 4), date(2013, 1, 21)];
+
+        assert_eq!(
+            networkdays(date(2012, 10, 1), date(2013, 3, 1), Some(&holidays)),
+            107
+        );
+        assert_eq!(networkdays(date(2006, 2, 28), date(2006, 1, 31), None), -21);
+        assert_eq!(networkdays(date(2021, 7, 31), date(2021, 7, 31), None), 0);
+        assert_eq!(networkdays(date(2021, 7, 30), date(2021, 7, 30), None), 1);
+    }
+
+    #[test]
+    fn networkdays_intl_honours_weekend() {
+        let holidays = [date(2006, 1, 2), date(2006, 1, 16)];
+
+        assert_eq!(
+            networkdays_intl(
+                date(2006, 1, 1),
+                date(2006, 2, 1),
+                Weekend::try_from(7).ok(),
+                Some(&holidays)
+            ),
+            22
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use crate::calendar::{BusinessDayConvention, Calendar};
+use crate::error::FinancialError;
+use crate::frequency::Frequency;
+use crate::naive_date::eomonth::eomonth;
+use chrono::NaiveDate;
+
+/// Where the irregular period of a schedule goes when the dates don't split into whole periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StubRule {
+    /// Dates are rolled back from the termination date, leaving a period shorter than the others at the start.
+    ShortFront,
+    /// Dates are rolled back from the termination date, merging the short period at the start into the next one.
+    LongFront,
+    /// Dates are rolled forward from the effective date, leaving a period shorter than the others at the end.
+    ShortBack,
+    /// Dates are rolled forward from the effective date, merging the short period at the end into the previous one.
+    LongBack,
+}
+
+/// The dates of a schedule, from the effective date to the termination date, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    /// The dates rolled from the effective or termination date.
+    pub unadjusted: Vec<NaiveDate>,
+    /// The unadjusted dates moved to business days.
+    pub adjusted: Vec<NaiveDate>,
+}
+
+/// Returns the dates of a schedule from `effective_date` to `termination_date` at `frequency`, the way ISDA
+/// schedules are built.
+///
+/// Regular dates are rolled from the termination date or the effective date depending on `stub`, keeping the
+/// day of month of that date, clamped to the end of shorter months. With `end_of_month`, a date rolled from
+/// the end of a month falls on the end of every month, for month based frequencies.
+/// The adjusted dates are the unadjusted ones moved to business days of `calendar` according to `convention`.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use financial::naive_date::StubRule;
+/// use financial::{BusinessDayConvention, Frequency, HolidayCalendar};
+///
+/// let schedule = financial::naive_date::schedule(
+///     NaiveDate::from_ymd_opt(2021, 1, 15).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 12, 18).unwrap(),
+///     Frequency::Quarterly,
+///     StubRule::ShortFront,
+///     false,
+///     &HolidayCalendar::default(),
+///     BusinessDayConvention::ModifiedFollowing,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(schedule.unadjusted[1], NaiveDate::from_ymd_opt(2021, 3, 18).unwrap());
+/// // September 18th, 2021 is a Saturday
+/// assert_eq!(schedule.unadjusted[3], NaiveDate::from_ymd_opt(2021, 9, 18).unwrap());
+/// assert_eq!(schedule.adjusted[3], NaiveDate::from_ymd_opt(2021, 9, 20).unwrap());
+///
+/// let cf = [-1000., 20., 30., 30., 1030.];
+/// let npv = financial::naive_date::xnpv(0.1, &cf, &schedule.adjusted).unwrap();
+/// ```
+pub fn schedule(
+    effective_date: NaiveDate,
+    termination_date: NaiveDate,
+    frequency: Frequency,
+    stub: StubRule,
+    end_of_month: bool,
+    calendar: &dyn Calendar,
+    convention: BusinessDayConvention,
+) -> Result<Schedule, FinancialError> {
+    if termination_date <= effective_date {
+        return Err(FinancialError::InvalidArgument {
+            name: "termination_date",
+            reason: "must be after effective_date",
+        });
+    }
+
+    let backward = matches!(stub, StubRule::ShortFront | StubRule::LongFront);
+    let (anchor, direction) = if backward {
+        (termination_date, -1)
+    } else {
+        (effective_date, 1)
+    };
+    let month_based = !matches!(frequency, Frequency::Weekly | Frequency::Biweekly);
+    let roll_to_end_of_month = end_of_month && month_based && eomonth(anchor, 0)? == anchor;
+
+    // regular dates strictly between the effective and termination dates, from the anchor
+    let mut regular = vec![];
+    for period in 1.. {
+        let mut date = frequency.advance(anchor, direction * period).ok_or(
+            FinancialError::InvalidArgument {
+                name: "frequency",
+                reason: "schedule dates are out of range",
+            },
+        )?;
+        if roll_to_end_of_month {
+            date = eomonth(date, 0)?;
+        }
+        if date <= effective_date || date >= termination_date {
+            let is_stub = date != effective_date && date != termination_date;
+            if is_stub && matches!(stub, StubRule::LongFront | StubRule::LongBack) {
+                regular.pop();
+            }
+            break;
+        }
+
+        regular.push(date);
+    }
+
+    if backward {
+        regular.reverse();
+    }
+
+    let unadjusted: Vec<NaiveDate> = std::iter::once(effective_date)
+        .chain(regular)
+        .chain(std::iter::once(termination_date))
+        .collect();
+    let adjusted = calendar.adjust_schedule(&unadjusted, convention);
+
+    Ok(Schedule {
+        unadjusted,
+        adjusted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::HolidayCalendar;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn unadjusted(
+        effective_date: NaiveDate,
+        termination_date: NaiveDate,
+        frequency: Frequency,
+        stub: StubRule,
+        end_of_month: bool,
+    ) -> Vec<NaiveDate> {
+        schedule(
+            effective_date,
+            termination_date,
+            frequency,
+            stub,
+            end_of_month,
+            &HolidayCalendar::default(),
+            BusinessDayConvention::Unadjusted,
+        )
+        .unwrap()
+        .unadjusted
+    }
+
+    #[test]
+    fn schedule_places_stubs() {
+        let dates = |stub| {
+            unadjusted(
+                date(2021, 1, 15),
+                date(2021, 12, 15),
+                Frequency::Quarterly,
+                stub,
+                false,
+            )
+        };
+
+        assert_eq!(
+            dates(StubRule::ShortFront),
+            vec![
+                date(2021, 1, 15),
+                date(2021, 3, 15),
+                date(2021, 6, 15),
+                date(2021, 9, 15),
+                date(2021, 12, 15)
+            ]
+        );
+        assert_eq!(
+            dates(StubRule::LongFront),
+            vec![
+                date(2021, 1, 15),
+                date(2021, 6, 15),
+                date(2021, 9, 15),
+                date(2021, 12, 15)
+            ]
+        );
+        assert_eq!(
+            dates(StubRule::ShortBack),
+            vec![
+                date(2021, 1, 15),
+                date(2021, 4, 15),
+                date(2021, 7, 15),
+                date(2021, 10, 15),
+                date(2021, 12, 15)
+            ]
+        );
+        assert_eq!(
+            dates(StubRule::LongBack),
+            vec![
+                date(2021, 1, 15),
+                date(2021, 4, 15),
+                date(2021, 7, 15),
+                date(2021, 12, 15)
+            ]
+        );
+    }
+
+    #[test]
+    fn schedule_without_stub_ignores_stub_rule() {
+        let expected = vec![date(2021, 1, 15), date(2021, 7, 15), date(2022, 1, 15)];
+
+        for stub in [
+            StubRule::ShortFront,
+            StubRule::LongFront,
+            StubRule::ShortBack,
+            StubRule::LongBack,
+        ] {
+            assert_eq!(
+                unadjusted(
+                    date(2021, 1, 15),
+                    date(2022, 1, 15),
+                    Frequency::SemiAnnually,
+                    stub,
+                    false
+                ),
+                expected
+            );
+        }
+
+        // a single period shorter than the frequency
+        assert_eq!(
+            unadjusted(
+                date(2021, 1, 15),
+                date(2021, 2, 1),
+                Frequency::Monthly,
+                StubRule::LongFront,
+                false
+            ),
+            vec![date(2021, 1, 15), date(2021, 2, 1)]
+        );
+    }
+
+    #[test]
+    fn schedule_follows_end_of_month_rule() {
+        let dates = |end_of_month| {
+            unadjusted(
+                date(2021, 2, 28),
+                date(2021, 5, 15),
+                Frequency::Monthly,
+                StubRule::ShortBack,
+                end_of_month,
+            )
+        };
+
+        assert_eq!(
+            dates(true),
+            vec![
+                date(2021, 2, 28),
+                date(2021, 3, 31),
+                date(2021, 4, 30),
+                date(2021, 5, 15)
+            ]
+        );
+        assert_eq!(
+            dates(false),
+            vec![
+                date(2021, 2, 28),
+                date(2021, 3, 28),
+                date(2021, 4, 28),
+                date(2021, 5, 15)
+            ]
+        );
+    }
+
+    #[test]
+    fn schedule_adjusts_dates() {
+        let calendar = HolidayCalendar::new(&[date(2021, 5, 31)]);
+        let schedule = schedule(
+            date(2021, 3, 31),
+            date(2021, 7, 31),
+            Frequency::Monthly,
+            StubRule::ShortFront,
+            true,
+            &calendar,
+            BusinessDayConvention::ModifiedFollowing,
+        )
+        .unwrap();
+
+        assert_eq!(
+            schedule.unadjusted,
+            vec![
+                date(2021, 3, 31),
+                date(2021, 4, 30),
+                date(2021, 5, 31),
+                date(2021, 6, 30),
+                date(2021, 7, 31)
+            ]
+        );
+        assert_eq!(
+            schedule.adjusted,
+            vec![
+                date(2021, 3, 31),
+                date(2021, 4, 30),
+                date(2021, 5, 28),
+                date(2021, 6, 30),
+                date(2021, 7, 30)
+            ]
+        );
+    }
+
+    #[test]
+    fn schedule_fails_on_invalid_dates() {
+        let calendar = HolidayCalendar::default();
+        let schedule = |effective_date, termination_date| {
+            schedule(
+                effective_date,
+                termination_date,
+                Frequency::Monthly,
+                StubRule::ShortFront,
+                false,
+                &calendar,
+                BusinessDayConvention::Following,
+            )
+        };
+
+        assert!(schedule(date(2021, 1, 1), date(2021, 1, 1)).is_err());
+        assert!(schedule(date(2021, 2, 1), date(2021, 1, 1)).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::calendar::{Calendar, HolidayCalendar, Weekend};
+use crate::error::FinancialError;
+use chrono::NaiveDate;
+
+/// Returns the date `days` business days before or after `start_date`, skipping Saturdays, Sundays and `holidays`,
+/// like Excel's `WORKDAY`.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2008, 10, 1).unwrap();
+/// let holidays = [
+///     NaiveDate::from_ymd_opt(2008, 11, 26).unwrap(),
+///     NaiveDate::from_ymd_opt(2008, 12, 4).unwrap(),
+///     NaiveDate::from_ymd_opt(2009, 1, 21).unwrap(),
+/// ];
+/// assert_eq!(
+///     financial::naive_date::workday(start, 151, Some(&holidays)).unwrap(),
+///     NaiveDate::from_ymd_opt(2009, 5, 5).unwrap()
+/// );
+/// ```
+pub fn workday(
+    start_date: NaiveDate,
+    days: i64,
+    holidays: Option<&[NaiveDate]>,
+) -> Result<NaiveDate, FinancialError> {
+    workday_intl(start_date, days, None, holidays)
+}
+
+/// Returns the date `days` business days before or after `start_date`, skipping the days of `weekend`
+/// (Saturday and Sunday by default) and `holidays`, like Excel's `WORKDAY.INTL`.
+///
+/// `start_date` is returned as is when `days` is zero, even if it isn't a business day.
+pub fn workday_intl(
+    start_date: NaiveDate,
+    days: i64,
+    weekend: Option<Weekend>,
+    holidays: Option<&[NaiveDate]>,
+) -> Result<NaiveDate, FinancialError> {
+    let calendar = HolidayCalendar::new(holidays.unwrap_or_default())
+        .with_weekend(weekend.unwrap_or_default());
+    let step: fn(&NaiveDate) -> Option<NaiveDate> = if days < 0 {
+        NaiveDate::pred_opt
+    } else {
+        NaiveDate::succ_opt
+    };
+    let out_of_range = FinancialError::InvalidArgument {
+        name: "days",
+        reason: "must keep the date within the range of NaiveDate",
+    };
+
+    let mut date = start_date;
+    for _ in 0..days.unsigned_abs() {
+        date = step(&date).ok_or(out_of_range)?;
+        while !calendar.is_business_day(date) {
+            date = step(&date).ok_or(out_of_range)?;
+        }
+    }
+
+    Ok(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn workday_works() {
+        let saturday = date(2021, 7, 31);
+
+        assert_eq!(
+            workday(date(2008, 10, 1), 151, None).unwrap(),
+            date(2009, 4, 30)
+        );
+        assert_eq!(workday(saturday, 0, None).unwrap(), saturday);
+        assert_eq!(workday(saturday, 1, None).unwrap(), date(2021, 8, 2));
+        assert_eq!(workday(saturday, -1, None).unwrap(), date(2021, 7, 30));
+        assert_eq!(
+            workday(date(2021, 12, 23), 1, Some(&[date(2021, 12, 24)])).unwrap(),
+            date(2021, 12, 27)
+        );
+    }
+
+    #[test]
+    fn workday_intl_honours_weekend() {
+        let weekend = Weekend::try_from(7).ok();
+
+        // Thursday to Sunday over a Friday and Saturday weekend
+        assert_eq!(
+            workday_intl(date(2021, 12, 23), 1, weekend, None).unwrap(),
+            date(2021, 12, 26)
+        );
+        assert_eq!(
+            workday_intl(date(2021, 12, 26), -1, weekend, None).unwrap(),
+            date(2021, 12, 23)
+        );
+    }
+
+    #[test]
+    fn workday_fails_out_of_range() {
+        assert!(workday(NaiveDate::MAX, 1, None).is_err());
+        assert!(workday(NaiveDate::MIN, -1, None).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::basis::Basis;
+use crate::day_count::{self, DayCount};
+use chrono::{Datelike, NaiveDate};
+
+/// Returns the fraction of a year between two dates, like Excel's `YEARFRAC`.
+///
+/// The dates can be given in any order, the result is never negative. `basis` defaults to `Basis::UsNasd30360`.
+///
+/// Bases 0 and 1 reproduce Excel rather than the conventions they're named after:
+/// - with basis 0, a start date at the end of February counts as the 30th, but the end date only does when it's
+///   also the end of February, and a 31st end date stays the 31st unless the start date is a 30th or 31st.
+/// - with basis 1, dates less than a year apart are divided by 366 when the period includes a February 29th
+///   (or lies in a leap year), by 365 otherwise. Longer periods are divided by the average length of the
+///   calendar years they span.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use financial::Basis;
+///
+/// let start = NaiveDate::from_ymd_opt(2012, 1, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2012, 7, 30).unwrap();
+/// let yearfrac = |basis| financial::naive_date::yearfrac(start, end, basis);
+/// assert!((yearfrac(None) - 0.58055556).abs() < 1e-7);
+/// assert!((yearfrac(Some(Basis::ActualActual)) - 0.57650273).abs() < 1e-7);
+/// assert!((yearfrac(Some(Basis::Actual365)) - 0.57808219).abs() < 1e-7);
+/// ```
+pub fn yearfrac(start_date: NaiveDate, end_date: NaiveDate, basis: Option<Basis>) -> f64 {
+    let (start, end) = if end_date < start_date {
+        (end_date, start_date)
+    } else {
+        (start_date, end_date)
+    };
+
+    match basis.unwrap_or_default() {
+        Basis::UsNasd30360 => us_nasd_30_360(start, end),
+        Basis::ActualActual => actual_actual(start, end),
+        basis => DayCount::from(basis).year_fraction(start, end),
+    }
+}
+
+fn us_nasd_30_360(start: NaiveDate, end: NaiveDate) -> f64 {
+    let (mut d1, mut d2) = (start.day(), end.day());
+
+    if d1 == 31 {
+        d1 = 30;
+        if d2 == 31 {
+            d2 = 30;
+        }
+    } else if d1 == 30 && d2 == 31 {
+        d2 = 30;
+    } else if day_count::is_end_of_february(start) {
+        d1 = 30;
+        if day_count::is_end_of_february(end) {
+            d2 = 30;
+        }
+    }
+
+    day_count::thirty_360(start, end, d1, d2)
+}
+
+fn actual_actual(start: NaiveDate, end: NaiveDate) -> f64 {
+    let actual_days = day_count::days_between(start, end);
+
+    let within_a_year = start.year() == end.year()
+        || (start.year() + 1 == end.year()
+            && (start.month(), start.day()) >= (end.month(), end.day()));
+
+    if within_a_year {
+        let includes_leap_day = (start.year() == end.year()
+            && day_count::days_in_year(start.year()) == 366.)
+            || is_leap_year_before_march(start)
+            || is_leap_year_from_march(end)
+            || (end.month() == 2 && end.day() == 29);
+
+        if includes_leap_day {
+            actual_days / 366.
+        } else {
+            actual_days / 365.
+        }
+    } else {
+        let years = end.year() - start.year() + 1;
+        let days_in_years: f64 = (start.year()..=end.year())
+            .map(day_count::days_in_year)
+            .sum();
+
+        actual_days / (days_in_years / years as f64)
+    }
+}
+
+fn is_leap_year_before_march(date: NaiveDate) -> bool {
+    day_count::days_in_year(date.year()) == 366. && date.month() < 3
+}
+
+fn is_leap_year_from_march(date: NaiveDate) -> bool {
+    day_count::days_in_year(date.year()) == 366. && date.month() >= 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn yearfrac_ignores_date_order() {
+        let (start, end) = (date(2020, 2, 29), date(2023, 8, 31));
+
+        for basis in 0..=4 {
+            let basis = Basis::try_from(basis).ok();
+            assert_eq!(yearfrac(start, end, basis), yearfrac(end, start, basis));
+            assert_eq!(yearfrac(start, start, basis), 0.);
+        }
+    }
+
+    #[test]
+    fn us_nasd_30_360_treats_end_of_february_like_excel() {
+        let basis = Some(Basis::UsNasd30360);
+
+        assert_eq!(
+            yearfrac(date(2021, 2, 28), date(2021, 3, 31), basis),
+            31. / 360.
+        );
+        assert_eq!(yearfrac(date(2021, 2, 28), date(2022, 2, 28), basis), 1.);
+        assert_eq!(
+            yearfrac(date(2021, 1, 31), date(2021, 2, 28), basis),
+            28. / 360.
+        );
+        assert_eq!(
+            yearfrac(date(2021, 1, 15), date(2021, 3, 31), basis),
+            76. / 360.
+        );
+    }
+
+    #[test]
+    fn actual_actual_averages_year_lengths_like_excel() {
+        let basis = Some(Basis::ActualActual);
+
+        assert_eq!(
+            yearfrac(date(2019, 3, 1), date(2020, 3, 1), basis),
+            366. / 366.
+        );
+        assert_eq!(
+            yearfrac(date(2021, 3, 1), date(2022, 3, 1), basis),
+            365. / 365.
+        );
+        assert_eq!(
+            yearfrac(date(2019, 6, 1), date(2020, 2, 29), basis),
+            273. / 366.
+        );
+        assert_eq!(
+            yearfrac(date(2019, 1, 1), date(2021, 1, 1), basis),
+            731. / ((365. + 366. + 365.) / 3.)
+        );
+    }
+}
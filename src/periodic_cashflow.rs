@@ -1,5 +1,18 @@
+pub mod amortization;
+pub mod convert_rate;
+pub mod cumipmt;
+pub mod cumprinc;
+pub mod effect;
 pub mod fv;
+pub mod ipmt;
 pub mod irr;
+pub mod irr_all;
+pub mod loan_schedule;
 pub mod mirr;
+pub mod nominal;
+pub mod nper;
 pub mod npv;
+pub mod pmt;
+pub mod ppmt;
 pub mod pv;
+pub mod rate;
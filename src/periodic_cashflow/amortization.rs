@@ -0,0 +1,220 @@
+use crate::common::utils;
+use crate::error::FinancialError;
+use crate::periodic_cashflow::pmt::pmt;
+
+/// A single period of an amortization schedule.
+///
+/// Amounts follow the same sign convention as `pmt`, `ipmt` and `ppmt`:
+/// `payment`, `interest` and `principal` have the opposite sign of the loan, while `balance` has its sign.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmortizationRow {
+    /// The period number, from 1 to `nper`.
+    pub period: u32,
+    /// The payment made in this period, interest and principal together.
+    pub payment: f64,
+    /// The interest paid in this period.
+    pub interest: f64,
+    /// The part of the payment that repays the loan.
+    pub principal: f64,
+    /// The balance left outstanding after this period's payment.
+    pub balance: f64,
+}
+
+/// The rows of an amortization schedule, one per period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmortizationSchedule {
+    /// The periods, in order.
+    pub rows: Vec<AmortizationRow>,
+}
+
+impl AmortizationSchedule {
+    /// Returns the sum of all payments.
+    pub fn total_payment(&self) -> f64 {
+        self.rows.iter().map(|row| row.payment).sum()
+    }
+
+    /// Returns the sum of the interest paid over the whole loan.
+    pub fn total_interest(&self) -> f64 {
+        self.rows.iter().map(|row| row.interest).sum()
+    }
+
+    /// Returns the sum of the principal repaid over the whole loan.
+    pub fn total_principal(&self) -> f64 {
+        self.rows.iter().map(|row| row.principal).sum()
+    }
+}
+
+/// Returns the amortization schedule of a level-payment loan.
+///
+/// `fv` is an optional balloon left outstanding after the last payment.
+/// When `decimals` is provided, payments and interest are rounded to that many decimal places.
+/// Either way, the last row is adjusted so the balance closes at the balloon (zero by default).
+/// When payments are made at the beginning of each period, the balloon is still due at the end of the last one,
+/// so the last row closes at the balloon discounted by that period, `-fv / (1 + rate)`.
+///
+/// # Examples
+///
+/// ```
+/// let schedule = financial::amortization_schedule(0.01, 12, 1000., None, None, Some(2)).unwrap();
+/// assert_eq!(schedule.rows.len(), 12);
+/// assert_eq!(schedule.rows[0].payment, -88.85);
+/// assert_eq!(schedule.rows[0].interest, -10.);
+/// assert_eq!(schedule.rows[11].balance, 0.);
+/// ```
+pub fn amortization_schedule(
+    rate: f64,
+    nper: u32,
+    pv: f64,
+    fv: Option<f64>,
+    pmt_at_begining: Option<bool>,
+    decimals: Option<u32>,
+) -> Result<AmortizationSchedule, FinancialError> {
+    if nper == 0 {
+        return Err(FinancialError::InvalidArgument {
+            name: "nper",
+            reason: "must be greater than zero",
+        });
+    }
+    if !rate.is_finite() || rate <= -1. {
+        return Err(FinancialError::InvalidArgument {
+            name: "rate",
+            reason: "must be finite and greater than -1",
+        });
+    }
+
+    let round = |x: f64| utils::round_to_decimals(x, decimals);
+
+    let at_begining = pmt_at_begining.unwrap_or(false);
+    let payment = round(pmt(rate, nper as f64, Some(pv), fv, pmt_at_begining));
+
+    // when paying at the beginning, the last period still accrues interest on what's left
+    let closing_balance = if at_begining {
+        -fv.unwrap_or(0.) / (1. + rate)
+    } else {
+        -fv.unwrap_or(0.)
+    };
+
+    let mut balance = pv;
+    let rows = (1..=nper)
+        .map(|period| {
+            let interest = if at_begining && period == 1 {
+                0.
+            } else {
+                round(-balance * rate)
+            };
+
+            let principal = if period == nper {
+                closing_balance - balance
+            } else {
+                payment - interest
+            };
+
+            balance = if period == nper {
+                closing_balance
+            } else {
+                balance + principal
+            };
+
+            AmortizationRow {
+                period,
+                payment: interest + principal,
+                interest,
+                principal,
+                balance,
+            }
+        })
+        .collect();
+
+    Ok(AmortizationSchedule { rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+    use crate::periodic_cashflow::{cumipmt::cumipmt, ipmt::ipmt, ppmt::ppmt, pv::pv};
+
+    #[test]
+    fn amortization_schedule_matches_ipmt_and_ppmt() {
+        let schedule = amortization_schedule(0.05, 10, 1000., None, Some(true), None).unwrap();
+
+        for row in schedule.rows.iter() {
+            let per = row.period as f64;
+            let ipmt = ipmt(0.05, per, 10., Some(1000.), None, Some(true)).unwrap();
+            let ppmt = ppmt(0.05, per, 10., Some(1000.), None, Some(true)).unwrap();
+            assert!(
+                (row.interest - ipmt).abs() <= PRECISION,
+                "period {}",
+                row.period
+            );
+            assert!(
+                (row.principal - ppmt).abs() <= PRECISION,
+                "period {}",
+                row.period
+            );
+        }
+    }
+
+    #[test]
+    fn amortization_schedule_totals_reconcile_with_pv() {
+        let schedule = amortization_schedule(0.09 / 12., 360, 125000., None, None, None).unwrap();
+        let payment = schedule.rows[0].payment;
+
+        assert_eq!(schedule.rows.last().unwrap().balance, 0.);
+        assert!((schedule.total_principal() - -125000.).abs() <= PRECISION);
+        assert!((pv(0.09 / 12., 360., Some(payment), None, None) - 125000.).abs() <= PRECISION);
+
+        let interest = cumipmt(0.09 / 12., 360., 125000., 1., 360., false).unwrap();
+        assert!((schedule.total_interest() - interest).abs() <= 1e-6);
+    }
+
+    #[test]
+    fn amortization_schedule_closes_at_balloon() {
+        let schedule =
+            amortization_schedule(0.01, 24, 10000., Some(-2000.), None, Some(2)).unwrap();
+
+        assert_eq!(schedule.rows.last().unwrap().balance, 2000.);
+        assert!((schedule.total_principal() - -8000.).abs() <= PRECISION);
+
+        let schedule =
+            amortization_schedule(0.01, 24, 10000., Some(-2000.), Some(true), Some(2)).unwrap();
+        let balance = schedule.rows.last().unwrap().balance;
+        assert!(
+            (balance - 2000. / 1.01).abs() <= PRECISION,
+            "got {}",
+            balance
+        );
+    }
+
+    #[test]
+    fn amortization_schedule_last_row_absorbs_rounding() {
+        let schedule = amortization_schedule(0.01, 12, 1000., None, None, Some(2)).unwrap();
+        let last = schedule.rows.last().unwrap();
+
+        assert!(schedule.rows[..11].iter().all(|row| row.payment == -88.85));
+        assert_eq!(last.balance, 0.);
+        assert!(
+            (last.payment - -88.84).abs() <= 0.01,
+            "got {}",
+            last.payment
+        );
+    }
+
+    #[test]
+    fn amortization_schedule_works_with_zero_rate() {
+        let schedule = amortization_schedule(0., 4, 1000., None, None, None).unwrap();
+
+        assert!(schedule
+            .rows
+            .iter()
+            .all(|row| row.payment == -250. && row.interest == 0.));
+        assert_eq!(schedule.rows.last().unwrap().balance, 0.);
+    }
+
+    #[test]
+    fn amortization_schedule_rejects_invalid_arguments() {
+        assert!(amortization_schedule(0.01, 0, 1000., None, None, None).is_err());
+        assert!(amortization_schedule(-1., 12, 1000., None, None, None).is_err());
+        assert!(amortization_schedule(f64::NAN, 12, 1000., None, None, None).is_err());
+    }
+}
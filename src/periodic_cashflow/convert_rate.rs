@@ -0,0 +1,123 @@
+use crate::error::FinancialError;
+
+/// How often interest is compounded within a year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compounding {
+    /// Compounded a given number of times per year, e.g. `Periodic(12)` for monthly and `Periodic(1)` for annually.
+    Periodic(u32),
+    /// Compounded continuously.
+    Continuous,
+}
+
+/// Converts an annual nominal rate compounded `from` into the equivalent annual nominal rate compounded `to`.
+///
+/// `Periodic(1)` is the effective annual rate, which is what `xnpv` expects.
+/// The rate per period, which `npv`, `fv` and `pv` expect, is the nominal rate divided by the number of periods per year.
+///
+/// # Examples
+///
+/// ```
+/// use financial::Compounding;
+/// // 12% APR compounded monthly as an effective annual rate
+/// let ear = financial::convert_rate(0.12, Compounding::Periodic(12), Compounding::Periodic(1)).unwrap();
+/// assert!((ear - 0.12682503013196977).abs() < 1e-7);
+///
+/// // the same rate compounded continuously
+/// let cont = financial::convert_rate(ear, Compounding::Periodic(1), Compounding::Continuous).unwrap();
+/// assert!((cont - 0.11940397023801703).abs() < 1e-7);
+/// ```
+pub fn convert_rate(rate: f64, from: Compounding, to: Compounding) -> Result<f64, FinancialError> {
+    if !rate.is_finite() {
+        return Err(FinancialError::InvalidArgument {
+            name: "rate",
+            reason: "must be finite",
+        });
+    }
+
+    // growth of a unit amount over one year
+    let growth = match from {
+        Compounding::Periodic(0) => {
+            return Err(FinancialError::InvalidArgument {
+                name: "from",
+                reason: "compounding periods must be greater than zero",
+            })
+        }
+        Compounding::Periodic(n) if 1. + rate / n as f64 <= 0. => {
+            return Err(FinancialError::InvalidArgument {
+                name: "rate",
+                reason: "must be greater than -1 per compounding period",
+            })
+        }
+        Compounding::Periodic(n) => f64::powf(1. + rate / n as f64, n as f64),
+        Compounding::Continuous => rate.exp(),
+    };
+
+    if to == Compounding::Periodic(0) {
+        return Err(FinancialError::InvalidArgument {
+            name: "to",
+            reason: "compounding periods must be greater than zero",
+        });
+    }
+
+    match to {
+        Compounding::Periodic(m) => Ok(m as f64 * (f64::powf(growth, 1. / m as f64) - 1.)),
+        Compounding::Continuous => Ok(growth.ln()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    #[test]
+    fn convert_rate_works_between_periodic() {
+        let quarterly =
+            convert_rate(0.12, Compounding::Periodic(12), Compounding::Periodic(4)).unwrap();
+        assert!(
+            (quarterly - 0.12120399999999965).abs() <= PRECISION,
+            "got {}",
+            quarterly
+        );
+    }
+
+    #[test]
+    fn convert_rate_works_with_continuous() {
+        let ear = convert_rate(0.05, Compounding::Continuous, Compounding::Periodic(1)).unwrap();
+        assert!(
+            (ear - 0.05127109637602412).abs() <= PRECISION,
+            "got {}",
+            ear
+        );
+
+        let back = convert_rate(ear, Compounding::Periodic(1), Compounding::Continuous).unwrap();
+        assert!((back - 0.05).abs() <= PRECISION, "got {}", back);
+    }
+
+    #[test]
+    fn convert_rate_to_same_compounding_is_identity() {
+        let rate =
+            convert_rate(0.07, Compounding::Periodic(365), Compounding::Periodic(365)).unwrap();
+        assert!((rate - 0.07).abs() <= PRECISION, "got {}", rate);
+    }
+
+    #[test]
+    fn convert_rate_works_with_more_periods_than_i32() {
+        // compounding that often is as good as continuous, up to the rounding of the rate per period
+        let rate = convert_rate(
+            0.05,
+            Compounding::Periodic(u32::MAX),
+            Compounding::Continuous,
+        )
+        .unwrap();
+        assert!((rate - 0.05).abs() <= 1e-5, "got {}", rate);
+    }
+
+    #[test]
+    fn convert_rate_rejects_invalid_arguments() {
+        assert!(convert_rate(0.1, Compounding::Periodic(0), Compounding::Continuous).is_err());
+        assert!(convert_rate(0.1, Compounding::Continuous, Compounding::Periodic(0)).is_err());
+        assert!(convert_rate(-2., Compounding::Periodic(1), Compounding::Continuous).is_err());
+        assert!(convert_rate(f64::NAN, Compounding::Periodic(1), Compounding::Continuous).is_err());
+    }
+}
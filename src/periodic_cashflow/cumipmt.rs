@@ -0,0 +1,124 @@
+use crate::error::FinancialError;
+use crate::periodic_cashflow::ipmt::ipmt;
+
+/// Returns the cumulative interest paid on a loan between `start_period` and `end_period`.
+///
+/// The arguments follow Excel's CUMIPMT: `rate`, `nper` and `pv` must be positive,
+/// and the periods must satisfy `1 <= start_period <= end_period <= nper`.
+/// Periods are truncated to integers.
+///
+/// # Examples
+///
+/// ```
+/// let interest = financial::cumipmt(0.09 / 12., 30. * 12., 125000., 13., 24., false).unwrap();
+/// assert!((interest - -11135.232130750845).abs() < 1e-7);
+/// ```
+pub fn cumipmt(
+    rate: f64,
+    nper: f64,
+    pv: f64,
+    start_period: f64,
+    end_period: f64,
+    pmt_at_begining: bool,
+) -> Result<f64, FinancialError> {
+    let (start_period, end_period) =
+        validate_cumulative_args(rate, nper, pv, start_period, end_period)?;
+
+    let mut interest = 0.;
+    for per in start_period..=end_period {
+        interest += ipmt(
+            rate,
+            per as f64,
+            nper,
+            Some(pv),
+            None,
+            Some(pmt_at_begining),
+        )?;
+    }
+
+    Ok(interest)
+}
+
+pub(crate) fn validate_cumulative_args(
+    rate: f64,
+    nper: f64,
+    pv: f64,
+    start_period: f64,
+    end_period: f64,
+) -> Result<(u32, u32), FinancialError> {
+    for (name, value) in [("rate", rate), ("nper", nper), ("pv", pv)].iter() {
+        if !value.is_finite() || *value <= 0. {
+            return Err(FinancialError::InvalidArgument {
+                name,
+                reason: "must be positive and finite",
+            });
+        }
+    }
+    for (name, value) in [("start_period", start_period), ("end_period", end_period)].iter() {
+        if !value.is_finite() {
+            return Err(FinancialError::InvalidArgument {
+                name,
+                reason: "must be finite",
+            });
+        }
+    }
+
+    let (start_period, end_period) = (start_period.trunc(), end_period.trunc());
+    if start_period < 1. || start_period > end_period {
+        return Err(FinancialError::InvalidArgument {
+            name: "start_period",
+            reason: "must be between 1 and end_period",
+        });
+    }
+    if end_period > nper {
+        return Err(FinancialError::InvalidArgument {
+            name: "end_period",
+            reason: "must not be greater than nper",
+        });
+    }
+
+    Ok((start_period as u32, end_period as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    #[test]
+    fn cumipmt_works() {
+        let interest = cumipmt(0.09 / 12., 360., 125000., 13., 24., false).unwrap();
+        assert!(
+            (interest - -11135.232130750845).abs() <= PRECISION,
+            "got {}",
+            interest
+        );
+    }
+
+    #[test]
+    fn cumipmt_works_for_single_period() {
+        let interest = cumipmt(0.09 / 12., 360., 125000., 1., 1., false).unwrap();
+        assert!((interest - -937.5).abs() <= PRECISION, "got {}", interest);
+    }
+
+    #[test]
+    fn cumipmt_works_when_pmt_at_beginning() {
+        assert_eq!(
+            cumipmt(0.09 / 12., 360., 125000., 1., 1., true).unwrap(),
+            0.
+        );
+    }
+
+    #[test]
+    fn cumipmt_rejects_invalid_arguments() {
+        assert!(cumipmt(0., 360., 125000., 1., 1., false).is_err());
+        assert!(cumipmt(0.01, 360., -125000., 1., 1., false).is_err());
+        assert!(cumipmt(0.01, 360., 125000., 0., 1., false).is_err());
+        assert!(cumipmt(0.01, 360., 125000., 5., 4., false).is_err());
+        assert!(cumipmt(0.01, 360., 125000., 1., 361., false).is_err());
+        assert!(cumipmt(f64::NAN, 360., 125000., 1., 1., false).is_err());
+        assert!(cumipmt(0.01, f64::INFINITY, 125000., 1., 1., false).is_err());
+        assert!(cumipmt(0.01, 360., 125000., 1., f64::NAN, false).is_err());
+        assert!(cumipmt(0.01, 360., 125000., f64::NAN, 1., false).is_err());
+    }
+}
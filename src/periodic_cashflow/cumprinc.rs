@@ -0,0 +1,83 @@
+use crate::error::FinancialError;
+use crate::periodic_cashflow::cumipmt::validate_cumulative_args;
+use crate::periodic_cashflow::ppmt::ppmt;
+
+/// Returns the cumulative principal paid on a loan between `start_period` and `end_period`.
+///
+/// The arguments follow Excel's CUMPRINC: `rate`, `nper` and `pv` must be positive,
+/// and the periods must satisfy `1 <= start_period <= end_period <= nper`.
+/// Periods are truncated to integers.
+///
+/// # Examples
+///
+/// ```
+/// let principal = financial::cumprinc(0.09 / 12., 30. * 12., 125000., 13., 24., false).unwrap();
+/// assert!((principal - -934.1071234208984).abs() < 1e-7);
+/// ```
+pub fn cumprinc(
+    rate: f64,
+    nper: f64,
+    pv: f64,
+    start_period: f64,
+    end_period: f64,
+    pmt_at_begining: bool,
+) -> Result<f64, FinancialError> {
+    let (start_period, end_period) =
+        validate_cumulative_args(rate, nper, pv, start_period, end_period)?;
+
+    let mut principal = 0.;
+    for per in start_period..=end_period {
+        principal += ppmt(
+            rate,
+            per as f64,
+            nper,
+            Some(pv),
+            None,
+            Some(pmt_at_begining),
+        )?;
+    }
+
+    Ok(principal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+    use crate::periodic_cashflow::{cumipmt::cumipmt, pmt::pmt};
+
+    #[test]
+    fn cumprinc_works() {
+        let principal = cumprinc(0.09 / 12., 360., 125000., 13., 24., false).unwrap();
+        assert!(
+            (principal - -934.1071234208984).abs() <= PRECISION,
+            "got {}",
+            principal
+        );
+    }
+
+    #[test]
+    fn cumprinc_works_for_single_period() {
+        let principal = cumprinc(0.09 / 12., 360., 125000., 1., 1., false).unwrap();
+        assert!(
+            (principal - -68.27827118097842).abs() <= PRECISION,
+            "got {}",
+            principal
+        );
+    }
+
+    #[test]
+    fn cumprinc_over_whole_loan_repays_pv() {
+        let principal = cumprinc(0.05, 10., 1000., 1., 10., true).unwrap();
+        assert!((principal - -1000.).abs() <= PRECISION, "got {}", principal);
+    }
+
+    #[test]
+    fn cumprinc_and_cumipmt_add_up_to_payments() {
+        let (rate, nper, pv) = (0.01, 24., 5000.);
+        let total = cumprinc(rate, nper, pv, 3., 8., false).unwrap()
+            + cumipmt(rate, nper, pv, 3., 8., false).unwrap();
+        let payments = 6. * pmt(rate, nper, Some(pv), None, None);
+        assert!((total - payments).abs() <= PRECISION, "got {}", total);
+    }
+}
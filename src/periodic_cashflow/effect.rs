@@ -0,0 +1,63 @@
+use crate::error::FinancialError;
+use crate::periodic_cashflow::convert_rate::{convert_rate, Compounding};
+
+/// Returns the effective annual interest rate, given the nominal annual interest rate and the number of compounding periods per year.
+///
+/// `npery` is truncated to an integer.
+///
+/// # Examples
+///
+/// ```
+/// let effect = financial::effect(0.0525, 4.).unwrap();
+/// assert!((effect - 0.053542667).abs() < 1e-7);
+/// ```
+pub fn effect(nominal_rate: f64, npery: f64) -> Result<f64, FinancialError> {
+    if !nominal_rate.is_finite() || nominal_rate <= 0. {
+        return Err(FinancialError::InvalidArgument {
+            name: "nominal_rate",
+            reason: "must be positive and finite",
+        });
+    }
+    if !npery.is_finite() || npery < 1. {
+        return Err(FinancialError::InvalidArgument {
+            name: "npery",
+            reason: "must be finite and at least 1",
+        });
+    }
+
+    convert_rate(
+        nominal_rate,
+        Compounding::Periodic(npery.trunc() as u32),
+        Compounding::Periodic(1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    #[test]
+    fn effect_works() {
+        let effect = effect(0.0525, 4.).unwrap();
+        assert!(
+            (effect - 0.05354266737075819).abs() <= PRECISION,
+            "got {}",
+            effect
+        );
+    }
+
+    #[test]
+    fn effect_truncates_npery() {
+        assert_eq!(effect(0.0525, 4.9).unwrap(), effect(0.0525, 4.).unwrap());
+    }
+
+    #[test]
+    fn effect_rejects_invalid_arguments() {
+        assert!(effect(0., 4.).is_err());
+        assert!(effect(0.0525, 0.5).is_err());
+        assert!(effect(f64::NAN, 4.).is_err());
+        assert!(effect(f64::INFINITY, 4.).is_err());
+        assert!(effect(0.0525, f64::NAN).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use num_traits::Float;
+
 /// Calculates the future value of an investment based on a constant interest rate.
 /// You can use FV with either periodic, constant payments, or a single lump sum payment.
 ///
@@ -14,22 +16,41 @@ pub fn fv(
     pv: Option<f64>,
     pmt_at_begining: Option<bool>,
 ) -> f64 {
-    let factor = |r| f64::powf(1.0 + r, nper);
+    fv_generic(rate, nper, pmt, pv, pmt_at_begining)
+}
 
-    let pmt = pmt.unwrap_or_else(|| 0.0);
-    let pv = pv.unwrap_or_else(|| 0.0);
+/// Calculates the future value like `fv`, for any floating point type.
+///
+/// # Examples
+///
+/// ```
+/// let fv = financial::generic::fv(0.1_f32, 5.0, Some(100.0), Some(1000.0), Some(false));
+/// assert!((fv - -2221.02).abs() < 1e-3);
+/// ```
+pub fn fv_generic<T: Float>(
+    rate: T,
+    nper: T,
+    pmt: Option<T>,
+    pv: Option<T>,
+    pmt_at_begining: Option<bool>,
+) -> T {
+    let one = T::one();
+    let factor = |r: T| (one + r).powf(nper);
+
+    let pmt = pmt.unwrap_or_else(T::zero);
+    let pv = pv.unwrap_or_else(T::zero);
 
-    if rate == 0.0 {
+    if rate.is_zero() {
         -(pv + pmt * nper)
     } else {
         let factor = factor(rate);
-        let pmt_at_begining = if pmt_at_begining.unwrap_or_else(|| false) {
-            1.0
+        let pmt_at_begining = if pmt_at_begining.unwrap_or(false) {
+            one
         } else {
-            0.0
+            T::zero()
         };
 
-        -pv * factor - pmt * (1.0 + rate * pmt_at_begining) / rate * (factor - 1.0)
+        -pv * factor - pmt * (one + rate * pmt_at_begining) / rate * (factor - one)
     }
 }
 
@@ -66,6 +87,17 @@ mod tests {
         assert_eq!(fv(0.0, 5.0, Some(100.0), Some(1000.0), None), -1500.0);
     }
 
+    #[test]
+    fn fv_generic_works_with_f32() {
+        let fv = fv_generic(0.1_f32, 5.0, Some(100.0), Some(1000.0), Some(true));
+        assert!(
+            (f64::from(fv) - -2282.071000000001).abs() < 1e-3,
+            "got {}",
+            fv
+        );
+        assert_eq!(fv_generic(0.0_f32, 5.0, Some(100.0), None, None), -500.0);
+    }
+
     #[test]
     fn fv_dummy() {
         let rates = [0.1, 0.1, 0.1, 0.0];
@@ -0,0 +1,98 @@
+use crate::error::FinancialError;
+use crate::periodic_cashflow::{fv::fv, pmt::pmt};
+
+/// Returns the interest payment for a given period for an investment based on periodic, constant payments and a constant interest rate.
+///
+/// `per` is the period for which you want to find the interest and must be in the range 1 to `nper`.
+///
+/// # Examples
+///
+/// ```
+/// let ipmt = financial::ipmt(0.1 / 12., 1.0, 36.0, Some(8000.0), None, None).unwrap();
+/// assert!((ipmt - -66.66666666666667).abs() < 1e-7);
+/// ```
+pub fn ipmt(
+    rate: f64,
+    per: f64,
+    nper: f64,
+    pv: Option<f64>,
+    fv: Option<f64>,
+    pmt_at_begining: Option<bool>,
+) -> Result<f64, FinancialError> {
+    if per < 1. || per > nper {
+        return Err(FinancialError::InvalidArgument {
+            name: "per",
+            reason: "must be between 1 and nper",
+        });
+    }
+
+    let at_begining = pmt_at_begining.unwrap_or(false);
+    if at_begining && per == 1. {
+        // the first payment is made before any interest is accrued
+        return Ok(0.);
+    }
+
+    let payment = pmt(rate, nper, pv, fv, pmt_at_begining);
+    let interest = self::fv(rate, per - 1., Some(payment), pv, pmt_at_begining) * rate;
+
+    if at_begining {
+        Ok(interest / (1. + rate))
+    } else {
+        Ok(interest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    #[test]
+    fn ipmt_works_first_period() {
+        let ipmt = ipmt(0.1 / 12., 1.0, 36.0, Some(8000.0), None, None).unwrap();
+        assert!(
+            (ipmt - -66.66666666666667).abs() <= PRECISION,
+            "got {}",
+            ipmt
+        );
+    }
+
+    #[test]
+    fn ipmt_works_last_period() {
+        let ipmt = ipmt(0.1, 3.0, 3.0, Some(8000.0), None, None).unwrap();
+        assert!(
+            (ipmt - -292.4471299093656).abs() <= PRECISION,
+            "got {}",
+            ipmt
+        );
+    }
+
+    #[test]
+    fn ipmt_works_when_pmt_at_beginning() {
+        assert_eq!(
+            ipmt(0.1, 1.0, 3.0, Some(8000.0), None, Some(true)).unwrap(),
+            0.
+        );
+
+        let ipmt = ipmt(0.1, 2.0, 3.0, Some(8000.0), None, Some(true)).unwrap();
+        assert!(
+            (ipmt - -507.5528700906345).abs() <= PRECISION,
+            "got {}",
+            ipmt
+        );
+    }
+
+    #[test]
+    fn ipmt_works_with_zero_rate() {
+        assert_eq!(ipmt(0.0, 2.0, 5.0, Some(1000.0), None, None).unwrap(), 0.);
+    }
+
+    #[test]
+    fn ipmt_rejects_out_of_range_period() {
+        assert!(matches!(
+            ipmt(0.1, 0.0, 3.0, Some(8000.0), None, None),
+            Err(FinancialError::InvalidArgument { name: "per", .. })
+        ));
+        assert!(ipmt(0.1, 4.0, 3.0, Some(8000.0), None, None).is_err());
+    }
+}
@@ -1,5 +1,8 @@
-use crate::common::{find_root::find_root, utils};
-use crate::periodic_cashflow::npv::npv;
+use crate::common::{find_root::solve, utils};
+use crate::error::FinancialError;
+use crate::periodic_cashflow::npv::{npv_derivative, npv_generic};
+use crate::solver::{SolveReport, SolverOptions};
+use num_traits::Float;
 
 /// Calculates the internal rate of return for a series of cash flows occurring at regular interval represented by the numbers in values.
 ///
@@ -9,39 +12,126 @@ use crate::periodic_cashflow::npv::npv;
 /// let guess = Some(0.);
 /// let cf_irr = financial::irr(&cf, guess);
 /// ```
-pub fn irr(values: &[f64], guess: Option<f64>) -> Result<f64, &'static str> {
-    let values = utils::trim_zeros(&values);
+pub fn irr(values: &[f64], guess: Option<f64>) -> Result<f64, FinancialError> {
+    irr_generic(values, guess)
+}
 
-    match utils::validate_cashflow_values(values) {
-        Err(x) => return Err(x),
-        Ok(()) => {}
-    }
+/// Calculates the internal rate of return like `irr`, for any floating point type.
+///
+/// For types less precise than `f64`, the solver accepts a root once NPV is within the rounding error of `values`.
+///
+/// # Example
+/// ```
+/// let cf = [-500_f32, 100., 100., 100., 100.];
+/// let irr = financial::generic::irr(&cf, None).unwrap();
+/// assert!((irr - -0.08364542).abs() < 1e-5);
+/// ```
+pub fn irr_generic<T: Float>(values: &[T], guess: Option<T>) -> Result<T, FinancialError> {
+    irr_generic_with_options(values, guess, &SolverOptions::default_for(values))
+}
 
-    let f_npv = |x: f64| npv(x, values);
+/// Calculates the internal rate of return like `irr_with_options`, for any floating point type.
+///
+/// The solver keeps its estimates in `f64`, like the rates and tolerances of `options`, and evaluates NPV in `T`.
+/// With `f32`, `options.f_tolerance` must be loosened to what NPV can resolve, which grows with the cashflows.
+///
+/// # Example
+/// ```
+/// let cf = [-500_000_f32, 1000., 120_000., 150_000., 180_000., 200_000.];
+/// let options = financial::SolverOptions {
+///     f_tolerance: 0.5,
+///     ..Default::default()
+/// };
+/// let irr = financial::generic::irr_with_options(&cf, None, &options).unwrap();
+/// assert!(financial::generic::npv(irr, &cf).abs() <= 0.5);
+/// ```
+pub fn irr_generic_with_options<T: Float>(
+    values: &[T],
+    guess: Option<T>,
+    options: &SolverOptions,
+) -> Result<T, FinancialError> {
+    solve_irr(values, guess.map(utils::cast), options, false)?
+        .into_result()
+        .map(utils::cast)
+}
 
-    // IRR is calculated by the bisection-search of a root of a polynomial.
-    // For it to work, we need to establish search bounds; we determine them by probing some values of the argument,
-    // starting from the initial guess (usually zero), and looking for npv to change its value.
-    // We are interested in a root closest to the initial guess, so we choose a conservative (close to 1.0) factor
-    // to expand the search area on each iteration.
-    let bounds_search_expansion_factor = 1.1;
+/// Calculates the internal rate of return like `irr`, with the solver tuned by `options`.
+///
+/// # Example
+/// ```
+/// let cf = [-500., 100., 100., 100., 100., 100.];
+/// let options = financial::SolverOptions {
+///     min_rate: -0.99,
+///     ..Default::default()
+/// };
+/// assert_eq!(financial::irr_with_options(&cf, None, &options).unwrap(), 0.);
+/// ```
+pub fn irr_with_options(
+    values: &[f64],
+    guess: Option<f64>,
+    options: &SolverOptions,
+) -> Result<f64, FinancialError> {
+    irr_with_report(values, guess, options, false)?.into_result()
+}
 
-    match find_root(guess, f_npv, bounds_search_expansion_factor) {
-        Some(ans) => Ok(ans),
-        None => Err("could't find irr for the values provided"),
-    }
+/// Runs the solver of `irr_with_options`, and reports how it went.
+///
+/// Invalid arguments are still returned as an error, but a solver that didn't converge returns a report without a root.
+/// Every estimate is recorded in `SolveReport::iterates` when `trace` is set.
+///
+/// # Example
+/// ```
+/// let cf = [-500., 100., 100., 100., 100.];
+/// let options = financial::SolverOptions {
+///     newton_max_iterations: 1,
+///     ..Default::default()
+/// };
+/// let report = financial::irr_with_report(&cf, None, &options, true).unwrap();
+/// assert_eq!(report.phase, financial::SolvePhase::Brent);
+/// assert!(report.root.is_some());
+/// assert!(report.residual.abs() < 1e-7);
+/// ```
+pub fn irr_with_report(
+    values: &[f64],
+    guess: Option<f64>,
+    options: &SolverOptions,
+    trace: bool,
+) -> Result<SolveReport, FinancialError> {
+    solve_irr(values, guess, options, trace)
+}
+
+fn solve_irr<T: Float>(
+    values: &[T],
+    guess: Option<f64>,
+    options: &SolverOptions,
+    trace: bool,
+) -> Result<SolveReport, FinancialError> {
+    let values = utils::trim_zeros(values);
+
+    utils::validate_cashflow_values(values)?;
+
+    let f_npv = |x: f64| utils::cast::<f64, T>(npv_generic(utils::cast(x), values));
+    let df_npv = |x: f64| utils::cast::<f64, T>(npv_derivative(utils::cast(x), values));
+
+    // IRR is calculated by Newton's method, falling back to a bracketed search of a root of a polynomial.
+    // For the latter to work, we need to establish search bounds; we determine them by probing some values of the argument,
+    // starting from the initial guess (usually zero), and looking for npv to change its value.
+    // We are interested in a root closest to the initial guess, so the default options choose a conservative
+    // (close to 1.0) factor to expand the search area on each iteration.
+    solve(guess, f_npv, df_npv, options, trace)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::common::PRECISION;
+    use crate::periodic_cashflow::npv::npv;
 
     #[test]
     fn irr_works_different_guess_sign() {
         let cf = [-500., 100., 100., 100., 100.];
         let guess = Some(0.);
-        let precision = (irr(&cf, guess).unwrap() - -0.08364541746615000000000000000000).abs();
+        let precision = (irr(&cf, guess).unwrap() - -0.08364541746615).abs();
         assert!(
             precision <= PRECISION,
             "IRR of {}, exceeded IRR precision threshold, {}",
@@ -54,7 +144,7 @@ mod tests {
     fn irr_works() {
         let cf = [-500., 100., 100., 100., 100.];
         let guess = Some(-0.);
-        let precision = (irr(&cf, guess).unwrap() - -0.08364541746615000000000000000000).abs();
+        let precision = (irr(&cf, guess).unwrap() - -0.08364541746615).abs();
         assert!(
             precision <= PRECISION,
             "exceeded {} IRR precision threshold, {}",
@@ -74,6 +164,107 @@ mod tests {
     fn irr_finds_closest_root_to_zero() {
         let cf = [10., 20., -10.];
         let guess = Some(0.);
-        assert_eq!(irr(&cf, guess).unwrap(), -0.5857864377789364); // there's also a solution of -3.414213531256609 which we don't want
+        let irr = irr(&cf, guess).unwrap();
+        assert!(
+            (irr - (f64::sqrt(2.) - 2.)).abs() <= PRECISION,
+            "got {}",
+            irr
+        ); // there's also a solution of -3.414213531256609 which we don't want
+    }
+
+    #[test]
+    fn irr_generic_works_with_f32() {
+        let cf = [-1000., 300., 400., 500., -100., 200.];
+        let cf_f32: Vec<f32> = cf.iter().map(|v| *v as f32).collect();
+        let irr_f32 = irr_generic(&cf_f32, None).unwrap();
+        let irr = irr(&cf, None).unwrap();
+
+        assert!(
+            (f64::from(irr_f32) - irr).abs() <= 1e-5,
+            "got {}, expected {}",
+            irr_f32,
+            irr
+        );
+        assert_eq!(
+            irr_generic(&[100_f32, 100.], None),
+            Err(FinancialError::NoSignChange)
+        );
+        assert!(irr_generic(&[-100_f32, 110.], Some(f32::NAN)).is_err());
+    }
+
+    #[test]
+    fn irr_fails_without_sign_change() {
+        let cf = [100., 100., 0., 100.];
+        assert_eq!(irr(&cf, None), Err(FinancialError::NoSignChange));
+    }
+
+    #[test]
+    fn irr_fails_on_invalid_values() {
+        assert!(irr(&[], None).is_err());
+        assert!(irr(&[0., 0., 0.], None).is_err());
+        assert!(irr(&[-100., f64::NAN, 100.], None).is_err());
+        assert!(irr(&[-100., f64::INFINITY, 100.], None).is_err());
+        assert!(irr(&[-100., 110.], Some(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn irr_with_options_honours_tolerance() {
+        let cf = [-1000., 300., 300., 300., 300.];
+        let options = SolverOptions {
+            x_tolerance: 1e-14,
+            f_tolerance: 1e-11,
+            ..Default::default()
+        };
+        let irr = irr_with_options(&cf, None, &options).unwrap();
+        assert!(
+            npv(irr, &cf).abs() <= 1e-11,
+            "npv at {} is {}",
+            irr,
+            npv(irr, &cf)
+        );
+    }
+
+    #[test]
+    fn irr_with_options_honours_rate_range() {
+        let cf = [10., 20., -10.];
+        let options = SolverOptions {
+            max_rate: -1.,
+            ..Default::default()
+        };
+        let irr = irr_with_options(&cf, None, &options).unwrap();
+        assert!((irr - -3.414213562373095).abs() <= PRECISION, "got {}", irr);
+    }
+
+    #[test]
+    fn irr_with_report_explains_failure() {
+        let cf = [-500., 100., 100., 100., 100.];
+        let options = SolverOptions {
+            newton_max_iterations: 3,
+            bracket_max_iterations: 2,
+            ..Default::default()
+        };
+        let report = irr_with_report(&cf, Some(10.), &options, true).unwrap();
+
+        assert_eq!(report.root, None);
+        assert_eq!(report.phase, crate::solver::SolvePhase::BracketSearch);
+        assert_eq!(report.newton_iterations, 3);
+        assert_eq!(report.bracket_search_iterations, 4);
+        assert_eq!(report.bracket, None);
+        assert_eq!(report.iterates.len(), 3);
+        assert_eq!(report.residual, npv(report.last_estimate, &cf));
+        assert!(matches!(
+            report.into_result(),
+            Err(FinancialError::DidNotConverge { iterations: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn irr_with_report_matches_irr() {
+        let cf = [-500., 150., 150., 150., 150.];
+        let report = irr_with_report(&cf, None, &SolverOptions::default(), false).unwrap();
+
+        assert_eq!(report.phase, crate::solver::SolvePhase::Newton);
+        assert_eq!(report.root, irr(&cf, None).ok());
+        assert!(report.iterates.is_empty());
     }
 }
@@ -0,0 +1,202 @@
+use crate::common::{root_isolation, utils};
+use crate::error::FinancialError;
+
+/// Every internal rate of return found within a range of rates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrrRoots {
+    /// The rates, in increasing order.
+    pub roots: Vec<f64>,
+    /// Whether the cash flow changes sign exactly once. Descartes' rule of signs then guarantees a single
+    /// rate of return above -100%, whatever `range` is, though rates below -100% may still exist.
+    /// This is sufficient but not necessary: a cash flow with several sign changes can still have a single
+    /// rate of return, see `is_unique`.
+    pub single_sign_change: bool,
+}
+
+impl IrrRoots {
+    /// Returns true if exactly one rate of return was found within the searched range.
+    pub fn is_unique(&self) -> bool {
+        self.roots.len() == 1
+    }
+}
+
+/// Returns all the internal rates of return within `range`, bounds included, for a series of cash flows
+/// occurring at regular interval.
+///
+/// Cash flows with more than one sign change can have several rates of return, or none. Unlike `irr`,
+/// which returns the root closest to its guess, this isolates every real root of the NPV polynomial.
+///
+/// Root isolation recurses on the derivatives of the NPV polynomial, one level per cash flow, so its cost
+/// grows quadratically with the number of cash flows: 5000 cash flows take about a second in release builds.
+/// Prefer `irr` for long cash flows that change sign once.
+///
+/// # Example
+/// ```
+/// let cf = [10., 20., -10.];
+/// let irr = financial::irr_all(&cf, (f64::NEG_INFINITY, f64::INFINITY)).unwrap();
+/// assert_eq!(irr.roots.len(), 2);
+/// assert!((irr.roots[0] - -3.414213562).abs() < 1e-7);
+/// assert!((irr.roots[1] - -0.585786437).abs() < 1e-7);
+/// // a single sign change only rules out a second rate above -100%
+/// assert!(irr.single_sign_change);
+/// assert!(!irr.is_unique());
+/// ```
+pub fn irr_all(values: &[f64], range: (f64, f64)) -> Result<IrrRoots, FinancialError> {
+    let values = utils::trim_zeros(values);
+
+    utils::validate_cashflow_values(values)?;
+
+    // npv is a polynomial in the discount factor 1 / (1 + rate), which is negative for rates below -100%
+    let terms: Vec<(f64, f64)> = values
+        .iter()
+        .enumerate()
+        .map(|(n, v)| (*v, n as f64))
+        .collect();
+
+    rates_in_range(&terms, range, true)
+}
+
+// Finds the rates in `range` where the generalized polynomial `terms` of the discount factor has a root.
+// Discount factors of rates below -100% are negative, their powers are only real for integer exponents.
+pub(crate) fn rates_in_range(
+    terms: &[(f64, f64)],
+    range: (f64, f64),
+    below_minus_one: bool,
+) -> Result<IrrRoots, FinancialError> {
+    let (lower, upper) = range;
+    if lower.is_nan() || upper.is_nan() || lower > upper {
+        return Err(FinancialError::InvalidArgument {
+            name: "range",
+            reason: "must be an increasing pair of rates",
+        });
+    }
+
+    let terms = root_isolation::normalize_terms(terms);
+    let discount_factor = |rate: f64| 1. / (1. + rate);
+    let mut roots = vec![];
+
+    // rates above -100%
+    if upper > -1. {
+        let bound = root_isolation::root_bound(&terms);
+        let x_lower = discount_factor(upper);
+        let x_upper = if lower <= -1. {
+            bound
+        } else {
+            discount_factor(lower).min(bound)
+        };
+
+        roots.extend(
+            root_isolation::positive_roots(&terms, x_lower, x_upper)
+                .into_iter()
+                .filter(|x| *x > 0.)
+                .map(|x| 1. / x - 1.),
+        );
+    }
+
+    // rates below -100%, through the positive roots of the polynomial of minus the discount factor
+    if below_minus_one && lower < -1. {
+        let reflected: Vec<(f64, f64)> = terms
+            .iter()
+            .map(|(coefficient, exponent)| {
+                if exponent % 2. == 0. {
+                    (*coefficient, *exponent)
+                } else {
+                    (-coefficient, *exponent)
+                }
+            })
+            .collect();
+        let bound = root_isolation::root_bound(&reflected);
+        let x_lower = -discount_factor(lower);
+        let x_upper = if upper >= -1. {
+            bound
+        } else {
+            (-discount_factor(upper)).min(bound)
+        };
+
+        roots.extend(
+            root_isolation::positive_roots(&reflected, x_lower, x_upper)
+                .into_iter()
+                .filter(|x| *x > 0.)
+                .map(|x| -1. / x - 1.),
+        );
+    }
+
+    roots.retain(|rate| lower <= *rate && *rate <= upper);
+    roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    roots.dedup();
+
+    Ok(IrrRoots {
+        roots,
+        single_sign_change: root_isolation::sign_changes(&terms) == 1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+    use crate::periodic_cashflow::{irr::irr, npv::npv};
+
+    const ALL_RATES: (f64, f64) = (f64::NEG_INFINITY, f64::INFINITY);
+
+    #[test]
+    fn irr_all_finds_both_roots() {
+        let cf = [10., 20., -10.];
+        let irr = irr_all(&cf, ALL_RATES).unwrap();
+
+        assert_eq!(irr.roots.len(), 2, "got {:?}", irr.roots);
+        assert!((irr.roots[0] - (-f64::sqrt(2.) - 2.)).abs() <= PRECISION);
+        assert!((irr.roots[1] - (f64::sqrt(2.) - 2.)).abs() <= PRECISION);
+
+        let above = irr_all(&cf, (-1., f64::INFINITY)).unwrap();
+        assert_eq!(above.roots, irr.roots[1..].to_vec());
+    }
+
+    #[test]
+    fn irr_all_finds_multiple_positive_roots() {
+        // npv is zero at 10%, 20% and 30%
+        let cf = [-1000., 3600., -4310., 1716.];
+        let irr = irr_all(&cf, (-0.99, 1.)).unwrap();
+
+        assert!(!irr.single_sign_change);
+        assert_eq!(irr.roots.len(), 3, "got {:?}", irr.roots);
+        for (root, expected) in irr.roots.iter().zip([0.1, 0.2, 0.3].iter()) {
+            assert!((root - expected).abs() <= PRECISION, "got {:?}", irr.roots);
+            assert!(npv(*root, &cf).abs() <= PRECISION);
+        }
+
+        assert!(!irr.is_unique());
+
+        // several sign changes, but a single rate within the range
+        let irr = irr_all(&cf, (0.15, 0.25)).unwrap();
+        assert_eq!(irr.roots.len(), 1, "got {:?}", irr.roots);
+        assert!(irr.is_unique());
+    }
+
+    #[test]
+    fn irr_all_agrees_with_irr() {
+        let cf = [-500., 100., 100., 100., 100.];
+        let irr_all = irr_all(&cf, (-1., f64::INFINITY)).unwrap();
+
+        assert!(irr_all.single_sign_change);
+        assert!(irr_all.is_unique());
+        assert!((irr_all.roots[0] - irr(&cf, None).unwrap()).abs() <= PRECISION);
+    }
+
+    #[test]
+    fn irr_all_can_find_no_root() {
+        // npv never reaches zero: 100 - 300x + 300x^2 > 0 for any discount factor x
+        let cf = [100., -300., 300.];
+        assert!(irr_all(&cf, ALL_RATES).unwrap().roots.is_empty());
+    }
+
+    #[test]
+    fn irr_all_fails_on_invalid_arguments() {
+        assert_eq!(
+            irr_all(&[100., 100.], ALL_RATES),
+            Err(FinancialError::NoSignChange)
+        );
+        assert!(irr_all(&[-100., 110.], (0.5, 0.1)).is_err());
+        assert!(irr_all(&[-100., 110.], (f64::NAN, 0.1)).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use crate::common::utils;
+use crate::error::FinancialError;
+use crate::periodic_cashflow::amortization::{
+    amortization_schedule, AmortizationRow, AmortizationSchedule,
+};
+use crate::periodic_cashflow::pmt::pmt;
+
+/// An event altering the level-payment schedule of a loan at a given period.
+///
+/// Amounts follow the sign convention of the schedule's payments, i.e. the opposite sign of the loan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoanEvent {
+    /// An extra principal payment made along with the scheduled payment of `period`.
+    ExtraPrincipal { period: u32, amount: f64 },
+    /// No payment is made at `period`, and the interest of the period is capitalised.
+    PaymentHoliday { period: u32 },
+    /// The interest rate changes to `rate` starting from `period`. The payment is kept unless the loan is recast.
+    RateChange { period: u32, rate: f64 },
+    /// After the payments of `period`, the payment is recalculated to amortize the remaining balance over the remaining periods.
+    Recast { period: u32 },
+}
+
+impl LoanEvent {
+    fn period(&self) -> u32 {
+        match *self {
+            LoanEvent::ExtraPrincipal { period, .. }
+            | LoanEvent::PaymentHoliday { period }
+            | LoanEvent::RateChange { period, .. }
+            | LoanEvent::Recast { period } => period,
+        }
+    }
+}
+
+/// A loan schedule after applying events, along with the interest saved compared to the baseline schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoanSchedule {
+    pub schedule: AmortizationSchedule,
+    pub baseline: AmortizationSchedule,
+    /// Positive when the events reduce the interest paid over the life of the loan.
+    pub interest_saved: f64,
+}
+
+/// Returns the schedule of a level-payment loan, paid at the end of each period, altered by a list of events.
+///
+/// The schedule ends early once the balance is paid off. Otherwise, the last row settles whatever balance is left
+/// so the schedule closes exactly at the balloon `fv` (zero by default).
+/// When `decimals` is provided, payments and interest are rounded to that many decimal places.
+///
+/// # Examples
+///
+/// ```
+/// use financial::LoanEvent;
+///
+/// let events = [LoanEvent::ExtraPrincipal { period: 1, amount: -500. }];
+/// let loan = financial::loan_schedule(0.01, 12, 1000., None, &events, Some(2)).unwrap();
+///
+/// assert_eq!(loan.schedule.rows.len(), 6);
+/// assert!(loan.interest_saved > 0.);
+/// ```
+pub fn loan_schedule(
+    rate: f64,
+    nper: u32,
+    pv: f64,
+    fv: Option<f64>,
+    events: &[LoanEvent],
+    decimals: Option<u32>,
+) -> Result<LoanSchedule, FinancialError> {
+    let baseline = amortization_schedule(rate, nper, pv, fv, None, decimals)?;
+
+    if events.iter().any(|e| e.period() == 0 || e.period() > nper) {
+        return Err(FinancialError::InvalidArgument {
+            name: "events",
+            reason: "periods must be between 1 and nper",
+        });
+    }
+    let invalid_rate = |e: &LoanEvent| match e {
+        LoanEvent::RateChange { rate, .. } => !rate.is_finite() || *rate <= -1.,
+        _ => false,
+    };
+    if events.iter().any(invalid_rate) {
+        return Err(FinancialError::InvalidArgument {
+            name: "events",
+            reason: "rate must be finite and greater than -1",
+        });
+    }
+
+    let round = |x: f64| utils::round_to_decimals(x, decimals);
+
+    let closing_balance = -fv.unwrap_or(0.);
+    let mut rate = rate;
+    let mut payment = round(pmt(rate, nper as f64, Some(pv), fv, None));
+    let mut balance = pv;
+    let mut rows = Vec::with_capacity(nper as usize);
+
+    for period in 1..=nper {
+        let period_events = || events.iter().filter(move |e| e.period() == period);
+
+        for event in period_events() {
+            if let LoanEvent::RateChange { rate: new_rate, .. } = event {
+                rate = *new_rate;
+            }
+        }
+
+        let holiday = period_events().any(|e| matches!(e, LoanEvent::PaymentHoliday { .. }));
+        let extra: f64 = period_events()
+            .map(|e| match e {
+                LoanEvent::ExtraPrincipal { amount, .. } => *amount,
+                _ => 0.,
+            })
+            .sum();
+
+        let interest = round(-balance * rate);
+        let scheduled = if holiday { 0. } else { payment };
+        let mut principal = scheduled + extra - interest;
+
+        // the balance is paid off when it reaches or crosses the closing balance
+        let paid_off = (balance + principal - closing_balance) * pv.signum() <= 0.;
+        if paid_off || period == nper {
+            principal = closing_balance - balance;
+        }
+
+        balance = if paid_off || period == nper {
+            closing_balance
+        } else {
+            balance + principal
+        };
+
+        rows.push(AmortizationRow {
+            period,
+            payment: interest + principal,
+            interest,
+            principal,
+            balance,
+        });
+
+        if paid_off {
+            break;
+        }
+
+        if period_events().any(|e| matches!(e, LoanEvent::Recast { .. })) {
+            payment = round(pmt(rate, (nper - period) as f64, Some(balance), fv, None));
+        }
+    }
+
+    let schedule = AmortizationSchedule { rows };
+    let interest_saved = (schedule.total_interest() - baseline.total_interest()) * pv.signum();
+
+    Ok(LoanSchedule {
+        schedule,
+        baseline,
+        interest_saved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    #[test]
+    fn loan_schedule_without_events_matches_baseline() {
+        let loan = loan_schedule(0.09 / 12., 360, 125000., None, &[], Some(2)).unwrap();
+
+        assert_eq!(loan.schedule, loan.baseline);
+        assert_eq!(loan.interest_saved, 0.);
+    }
+
+    #[test]
+    fn loan_schedule_extra_principal_pays_off_early() {
+        let events = [LoanEvent::ExtraPrincipal {
+            period: 1,
+            amount: -500.,
+        }];
+        let loan = loan_schedule(0.01, 12, 1000., None, &events, None).unwrap();
+        let first = loan.schedule.rows[0];
+
+        assert!((first.balance - (1010. - 88.84878867834167 - 500.)).abs() <= PRECISION);
+        assert_eq!(loan.schedule.rows.len(), 6);
+        assert_eq!(loan.schedule.rows.last().unwrap().balance, 0.);
+        assert!((loan.schedule.total_principal() - -1000.).abs() <= PRECISION);
+        assert!(loan.interest_saved > 0.);
+    }
+
+    #[test]
+    fn loan_schedule_payment_holiday_capitalises_interest() {
+        let events = [LoanEvent::PaymentHoliday { period: 1 }];
+        let loan = loan_schedule(0.01, 12, 1000., None, &events, None).unwrap();
+        let first = loan.schedule.rows[0];
+
+        assert_eq!(first.payment, 0.);
+        assert!((first.balance - 1010.).abs() <= PRECISION);
+        assert_eq!(loan.schedule.rows.len(), 12);
+        assert_eq!(loan.schedule.rows.last().unwrap().balance, 0.);
+        assert!(loan.interest_saved < 0.);
+    }
+
+    #[test]
+    fn loan_schedule_rate_change_keeps_payment() {
+        let events = [LoanEvent::RateChange {
+            period: 7,
+            rate: 0.02,
+        }];
+        let loan = loan_schedule(0.01, 12, 1000., None, &events, None).unwrap();
+        let rows = &loan.schedule.rows;
+
+        assert_eq!(rows[6].payment, rows[0].payment);
+        assert!((rows[6].interest - -rows[5].balance * 0.02).abs() <= PRECISION);
+        assert!(rows[11].payment < rows[0].payment);
+    }
+
+    #[test]
+    fn loan_schedule_recast_after_lump_sum() {
+        let events = [
+            LoanEvent::ExtraPrincipal {
+                period: 6,
+                amount: -200.,
+            },
+            LoanEvent::Recast { period: 6 },
+        ];
+        let loan = loan_schedule(0.01, 12, 1000., None, &events, None).unwrap();
+        let rows = &loan.schedule.rows;
+        let recast = pmt(0.01, 6., Some(rows[5].balance), None, None);
+
+        assert_eq!(rows.len(), 12);
+        assert!(rows[6..]
+            .iter()
+            .all(|row| (row.payment - recast).abs() <= PRECISION));
+        assert!(loan.interest_saved > 0.);
+    }
+
+    #[test]
+    fn loan_schedule_rejects_invalid_events() {
+        let events = [LoanEvent::Recast { period: 13 }];
+        assert!(loan_schedule(0.01, 12, 1000., None, &events, None).is_err());
+
+        let events = [LoanEvent::RateChange {
+            period: 2,
+            rate: -1.,
+        }];
+        assert!(loan_schedule(0.01, 12, 1000., None, &events, None).is_err());
+
+        let events = [LoanEvent::RateChange {
+            period: 2,
+            rate: f64::NAN,
+        }];
+        assert!(loan_schedule(0.01, 12, 1000., None, &events, None).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::common::utils;
+use num_traits::Float;
 
 /// Returns the modified internal rate of return for a series of periodic cash flows.
 /// MIRR considers both the cost of the investment and the interest received on reinvestment of cash.
@@ -11,23 +12,47 @@ use crate::common::utils;
 /// let mirr = financial::mirr(&cf, finance_rate, reinvest_rate);
 /// ```
 pub fn mirr(values: &[f64], finance_rate: f64, reinvest_rate: f64) -> f64 {
+    mirr_generic(values, finance_rate, reinvest_rate)
+}
+
+/// Returns the modified internal rate of return like `mirr`, for any floating point type.
+///
+/// # Example
+/// ```
+/// let cf = [-1000_f32, 100., 200., 300., 400., 400., 400.];
+/// let mirr = financial::generic::mirr(&cf, 0.1, 0.1);
+/// assert!((mirr - 0.13845383).abs() < 1e-5);
+/// ```
+pub fn mirr_generic<T: Float>(values: &[T], finance_rate: T, reinvest_rate: T) -> T {
     let values = utils::trim_end_zeros(values);
+    let one = T::one();
 
-    let negative_cf_pv : f64 = utils::powers(1. + finance_rate, values.len(), true)
-    .iter()
-    .zip(values.iter())
-    .filter(|(_r, &v)| v < 0.)
-    .map(|(r, v)| *v / *r)
-    .sum();
+    // negative values discounted to the first period, and positive values compounded to the last,
+    // accumulating the factors as the values are read
+    let (finance_base, mut finance_factor) = (one + finance_rate, one);
+    let negative_cf_pv = values.iter().fold(T::zero(), |sum, v| {
+        let pv = if *v < T::zero() {
+            sum + *v / finance_factor
+        } else {
+            sum
+        };
+        finance_factor = finance_factor * finance_base;
+        pv
+    });
 
-    let positive_cf_fv : f64 = utils::powers(1. + reinvest_rate, values.len(), true)
-    .iter()
-    .zip(values.iter().rev())
-    .filter(|(_r, &v)| v > 0.)
-    .map(|(r, v)| v * r)
-    .sum();
+    let (reinvest_base, mut reinvest_factor) = (one + reinvest_rate, one);
+    let positive_cf_fv = values.iter().rev().fold(T::zero(), |sum, v| {
+        let fv = if *v > T::zero() {
+            sum + *v * reinvest_factor
+        } else {
+            sum
+        };
+        reinvest_factor = reinvest_factor * reinvest_base;
+        fv
+    });
 
-    (positive_cf_fv / -negative_cf_pv).powf(1. / (values.len() - 1) as f64) - 1.
+    let periods: T = utils::cast(values.len() as f64 - 1.);
+    (positive_cf_fv / -negative_cf_pv).powf(one / periods) - one
 }
 
 #[cfg(test)]
@@ -44,10 +69,10 @@ mod tests {
 
         let ans = 0.138453832579;
         assert!(
-           (mirr - ans).abs() <= PRECISION,
-           "ans is {} got {}",
-           ans,
-           mirr
+            (mirr - ans).abs() <= PRECISION,
+            "ans is {} got {}",
+            ans,
+            mirr
         );
     }
 
@@ -60,10 +85,25 @@ mod tests {
 
         let ans = 0.16288556821502476;
         assert!(
-           (mirr - ans).abs() <= PRECISION,
-           "ans is {} got {}",
-           ans,
-           mirr
+            (mirr - ans).abs() <= PRECISION,
+            "ans is {} got {}",
+            ans,
+            mirr
+        );
+    }
+
+    #[test]
+    fn mirr_generic_works_with_f32() {
+        let cf = [-100_000., 18_000., -50_000., 25_000., 25_000., 225_000.];
+        let cf_f32: Vec<f32> = cf.iter().map(|v| *v as f32).collect();
+        let mirr = mirr_generic(&cf_f32, 0.05, 0.1);
+
+        let ans = mirr_generic(&cf, 0.05, 0.1);
+        assert!(
+            (f64::from(mirr) - ans).abs() <= 1e-6,
+            "ans is {} got {}",
+            ans,
+            mirr
         );
     }
 
@@ -75,11 +115,6 @@ mod tests {
         let mirr = mirr(&cf, finance_rate, reinvest_rate);
 
         let ans = f64::INFINITY;
-        assert!(
-           mirr.is_infinite(),
-           "ans is {} got {}",
-           ans,
-           mirr
-        );
+        assert!(mirr.is_infinite(), "ans is {} got {}", ans, mirr);
     }
 }
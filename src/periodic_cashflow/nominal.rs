@@ -0,0 +1,64 @@
+use crate::error::FinancialError;
+use crate::periodic_cashflow::convert_rate::{convert_rate, Compounding};
+
+/// Returns the nominal annual interest rate, given the effective rate and the number of compounding periods per year.
+///
+/// `npery` is truncated to an integer.
+///
+/// # Examples
+///
+/// ```
+/// let nominal = financial::nominal(0.053543, 4.).unwrap();
+/// assert!((nominal - 0.052500319).abs() < 1e-7);
+/// ```
+pub fn nominal(effect_rate: f64, npery: f64) -> Result<f64, FinancialError> {
+    if !effect_rate.is_finite() || effect_rate <= 0. {
+        return Err(FinancialError::InvalidArgument {
+            name: "effect_rate",
+            reason: "must be positive and finite",
+        });
+    }
+    if !npery.is_finite() || npery < 1. {
+        return Err(FinancialError::InvalidArgument {
+            name: "npery",
+            reason: "must be finite and at least 1",
+        });
+    }
+
+    convert_rate(
+        effect_rate,
+        Compounding::Periodic(1),
+        Compounding::Periodic(npery.trunc() as u32),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+    use crate::periodic_cashflow::effect::effect;
+
+    #[test]
+    fn nominal_works() {
+        let nominal = nominal(0.053543, 4.).unwrap();
+        assert!(
+            (nominal - 0.052500319868356016).abs() <= PRECISION,
+            "got {}",
+            nominal
+        );
+    }
+
+    #[test]
+    fn nominal_reverses_effect() {
+        let nominal = nominal(effect(0.08, 12.).unwrap(), 12.).unwrap();
+        assert!((nominal - 0.08).abs() <= PRECISION, "got {}", nominal);
+    }
+
+    #[test]
+    fn nominal_rejects_invalid_arguments() {
+        assert!(nominal(-0.1, 4.).is_err());
+        assert!(nominal(0.1, 0.).is_err());
+        assert!(nominal(f64::NAN, 4.).is_err());
+        assert!(nominal(f64::NEG_INFINITY, 4.).is_err());
+    }
+}
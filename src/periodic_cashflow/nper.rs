@@ -0,0 +1,128 @@
+use crate::error::FinancialError;
+
+/// Returns the number of periods for an investment based on periodic, constant payments and a constant interest rate.
+///
+/// Returns an error when no number of periods can satisfy the arguments,
+/// e.g. when the payment doesn't cover the interest accrued on the loan.
+///
+/// # Examples
+///
+/// ```
+/// let nper = financial::nper(0.01, Some(-100.0), Some(-1000.0), Some(10000.0), Some(true)).unwrap();
+/// assert!((nper - 59.67386567).abs() < 1e-7);
+/// ```
+pub fn nper(
+    rate: f64,
+    pmt: Option<f64>,
+    pv: Option<f64>,
+    fv: Option<f64>,
+    pmt_at_begining: Option<bool>,
+) -> Result<f64, FinancialError> {
+    let pmt = pmt.unwrap_or(0.);
+    let pv = pv.unwrap_or(0.);
+    let fv = fv.unwrap_or(0.);
+
+    if rate == 0.0 {
+        if pmt == 0. {
+            return Err(FinancialError::InvalidArgument {
+                name: "pmt",
+                reason: "cannot be zero when rate is zero",
+            });
+        }
+        return Ok(-(pv + fv) / pmt);
+    }
+
+    if rate <= -1. {
+        return Err(FinancialError::InvalidArgument {
+            name: "rate",
+            reason: "must be greater than -1",
+        });
+    }
+
+    let pmt_at_begining = if pmt_at_begining.unwrap_or(false) {
+        1.
+    } else {
+        0.
+    };
+    let z = pmt * (1. + rate * pmt_at_begining) / rate;
+    let ratio = (z - fv) / (z + pv);
+
+    if !ratio.is_finite() || ratio <= 0. {
+        return Err(FinancialError::NoSolution);
+    }
+
+    Ok(ratio.ln() / rate.ln_1p())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    #[test]
+    fn nper_works_when_pmt_at_end_of_period() {
+        let nper = nper(
+            0.01,
+            Some(-100.0),
+            Some(-1000.0),
+            Some(10000.0),
+            Some(false),
+        )
+        .unwrap();
+        assert!(
+            (nper - 60.08212285376166).abs() <= PRECISION,
+            "got {}",
+            nper
+        );
+    }
+
+    #[test]
+    fn nper_works_when_pmt_at_beginning() {
+        let nper = nper(0.01, Some(-100.0), Some(-1000.0), Some(10000.0), Some(true)).unwrap();
+        assert!(
+            (nper - 59.67386567429457).abs() <= PRECISION,
+            "got {}",
+            nper
+        );
+    }
+
+    #[test]
+    fn nper_works_with_pv_only() {
+        let nper = nper(0.01, Some(-100.0), Some(-1000.0), None, None).unwrap();
+        assert!(
+            (nper - -9.578594039813161).abs() <= PRECISION,
+            "got {}",
+            nper
+        );
+    }
+
+    #[test]
+    fn nper_works_with_zero_rate() {
+        assert_eq!(
+            nper(0.0, Some(-100.0), Some(1000.0), None, None).unwrap(),
+            10.0
+        );
+        assert!(nper(0.0, None, Some(1000.0), None, None).is_err());
+    }
+
+    #[test]
+    fn nper_reverses_fv() {
+        let nper = nper(
+            0.1,
+            Some(100.0),
+            Some(1000.0),
+            Some(-2221.020000000001),
+            None,
+        )
+        .unwrap();
+        assert!((nper - 5.0).abs() <= PRECISION, "got {}", nper);
+    }
+
+    #[test]
+    fn nper_fails_when_pmt_does_not_cover_interest() {
+        assert_eq!(
+            nper(0.1, Some(-50.0), Some(1000.0), None, None),
+            Err(FinancialError::NoSolution)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::common::utils;
+use num_traits::Float;
 
 /// Calculates the net present value of an investment by using a discount rate and a series of future payments
 /// (negative values) and income (positive values).
@@ -9,17 +9,43 @@ use crate::common::utils;
 /// let npv = financial::npv(0.1, &[-1000., 500., 500., 500.]);
 /// assert_eq!(npv, 221.29635953828267);
 /// ```
-// pre calculating powers for performance
 pub fn npv(rate: f64, values: &[f64]) -> f64 {
-    if rate == 0.0 {
-        return values.iter().sum();
+    npv_generic(rate, values)
+}
+
+/// Calculates the net present value like `npv`, for any floating point type.
+///
+/// # Examples
+///
+/// ```
+/// let npv = financial::generic::npv(0.1_f32, &[-1000., 500., 500., 500.]);
+/// assert!((npv - 221.29636).abs() < 1e-3);
+/// ```
+// discount factors are accumulated as the values are read, without allocating nor calling powf
+pub fn npv_generic<T: Float>(rate: T, values: &[T]) -> T {
+    if rate.is_zero() {
+        return values.iter().fold(T::zero(), |sum, v| sum + *v);
     }
 
-    utils::powers(1. + rate, values.len(), false)
-        .iter()
-        .zip(values.iter())
-        .map(|(p, v)| v / p)
-        .sum()
+    let base = T::one() + rate;
+    let mut factor = T::one();
+    values.iter().fold(T::zero(), |sum, v| {
+        factor = factor * base;
+        sum + *v / factor
+    })
+}
+
+/// Derivative of `npv` with respect to `rate`.
+pub(crate) fn npv_derivative<T: Float>(rate: T, values: &[T]) -> T {
+    let base = T::one() + rate;
+    let mut factor = T::one();
+    let mut n = T::zero();
+
+    values.iter().fold(T::zero(), |sum, v| {
+        factor = factor * base;
+        n = n + T::one();
+        sum + -n * *v / (factor * base)
+    })
 }
 
 #[cfg(test)]
@@ -46,4 +72,39 @@ mod tests {
         let rate = 0.1;
         assert_eq!(npv(rate, &cf), npv_slow(&rate, &cf));
     }
+
+    #[test]
+    fn npv_generic_works_with_f32() {
+        let cf = [-1000., 300., 400., 500.];
+        let cf_f32: Vec<f32> = cf.iter().map(|v| *v as f32).collect();
+
+        for rate in [-0.5, 0., 0.1, 2.].iter() {
+            let expected = npv(*rate, &cf);
+            let result = npv_generic(*rate as f32, &cf_f32);
+            assert!(
+                (f64::from(result) - expected).abs() <= 1e-6 * expected.abs().max(1.),
+                "rate {}: got {}, expected {}",
+                rate,
+                result,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn npv_derivative_matches_finite_difference() {
+        let cf = [-1000., 300., 400., 500.];
+        let h = 1e-6;
+        for rate in [-0.5, 0., 0.1, 2.].iter() {
+            let expected = (npv(rate + h, &cf) - npv(rate - h, &cf)) / (2. * h);
+            let derivative = npv_derivative(*rate, &cf);
+            assert!(
+                (derivative - expected).abs() <= 1e-4 * expected.abs(),
+                "rate {}: got {}, expected {}",
+                rate,
+                derivative,
+                expected
+            );
+        }
+    }
 }
@@ -0,0 +1,83 @@
+/// Calculates the payment for a loan based on constant payments and a constant interest rate.
+///
+/// # Examples
+///
+/// ```
+/// let pmt = financial::pmt(0.08 / 12., 10.0, Some(10000.0), None, None);
+/// assert!((pmt - -1037.0320893591522).abs() < 1e-7);
+/// ```
+pub fn pmt(
+    rate: f64,
+    nper: f64,
+    pv: Option<f64>,
+    fv: Option<f64>,
+    pmt_at_begining: Option<bool>,
+) -> f64 {
+    let pv = pv.unwrap_or(0.);
+    let fv = fv.unwrap_or(0.);
+
+    if rate == 0.0 {
+        -(pv + fv) / nper
+    } else {
+        let pmt_at_begining = if pmt_at_begining.unwrap_or(false) {
+            1.
+        } else {
+            0.
+        };
+        let factor = f64::powf(1. + rate, nper);
+        -(pv * factor + fv) * rate / ((1. + rate * pmt_at_begining) * (factor - 1.))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    #[test]
+    fn pmt_works_when_pmt_at_end_of_period() {
+        let pmt = pmt(0.08 / 12., 10.0, Some(10000.0), None, Some(false));
+        assert!(
+            (pmt - -1037.0320893591522).abs() <= PRECISION,
+            "got {}",
+            pmt
+        );
+    }
+
+    #[test]
+    fn pmt_works_when_pmt_at_beginning() {
+        let pmt = pmt(0.08 / 12., 10.0, Some(10000.0), None, Some(true));
+        assert!(
+            (pmt - -1030.1643271779658).abs() <= PRECISION,
+            "got {}",
+            pmt
+        );
+    }
+
+    #[test]
+    fn pmt_works_with_fv_only() {
+        let pmt = pmt(0.06 / 12., 18. * 12., None, Some(50000.0), None);
+        assert!(
+            (pmt - -129.08116086799092).abs() <= PRECISION,
+            "got {}",
+            pmt
+        );
+    }
+
+    #[test]
+    fn pmt_works_with_zero_rate() {
+        assert_eq!(pmt(0.0, 5.0, Some(1000.0), Some(500.0), None), -300.0);
+    }
+
+    #[test]
+    fn pmt_reverses_fv() {
+        let pmt = pmt(
+            0.1,
+            5.0,
+            Some(1000.0),
+            Some(-2221.020000000001),
+            Some(false),
+        );
+        assert!((pmt - 100.0).abs() <= PRECISION, "got {}", pmt);
+    }
+}
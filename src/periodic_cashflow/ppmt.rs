@@ -0,0 +1,57 @@
+use crate::error::FinancialError;
+use crate::periodic_cashflow::{ipmt::ipmt, pmt::pmt};
+
+/// Returns the payment on the principal for a given period for an investment based on periodic, constant payments and a constant interest rate.
+///
+/// `per` is the period for which you want to find the principal and must be in the range 1 to `nper`.
+///
+/// # Examples
+///
+/// ```
+/// let ppmt = financial::ppmt(0.08, 10.0, 10.0, Some(200000.0), None, None).unwrap();
+/// assert!((ppmt - -27598.053462421376).abs() < 1e-7);
+/// ```
+pub fn ppmt(
+    rate: f64,
+    per: f64,
+    nper: f64,
+    pv: Option<f64>,
+    fv: Option<f64>,
+    pmt_at_begining: Option<bool>,
+) -> Result<f64, FinancialError> {
+    let interest = ipmt(rate, per, nper, pv, fv, pmt_at_begining)?;
+    Ok(pmt(rate, nper, pv, fv, pmt_at_begining) - interest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    #[test]
+    fn ppmt_works() {
+        let ppmt = ppmt(0.1 / 12., 1.0, 24.0, Some(2000.0), None, None).unwrap();
+        assert!(
+            (ppmt - -75.62318600836634).abs() <= PRECISION,
+            "got {}",
+            ppmt
+        );
+    }
+
+    #[test]
+    fn ppmt_and_ipmt_add_up_to_pmt() {
+        let (rate, nper, pv) = (0.05, 10.0, Some(1000.0));
+        let payment = pmt(rate, nper, pv, None, Some(true));
+        for per in 1..=10 {
+            let per = per as f64;
+            let sum = ppmt(rate, per, nper, pv, None, Some(true)).unwrap()
+                + ipmt(rate, per, nper, pv, None, Some(true)).unwrap();
+            assert!((sum - payment).abs() <= PRECISION);
+        }
+    }
+
+    #[test]
+    fn ppmt_rejects_out_of_range_period() {
+        assert!(ppmt(0.1, 11.0, 10.0, Some(200000.0), None, None).is_err());
+    }
+}
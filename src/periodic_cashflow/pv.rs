@@ -1,3 +1,5 @@
+use num_traits::Float;
+
 /// Calculates the present value of a loan or an investment, based on a constant interest rate.
 /// You can use PV with either periodic, constant payments (such as a mortgage or other loan),
 /// or a future value that's your investment goal.
@@ -15,19 +17,38 @@ pub fn pv(
     fv: Option<f64>,
     pmt_at_begining: Option<bool>,
 ) -> f64 {
-    let pmt = pmt.unwrap_or_else(|| 0.);
-    let fv = fv.unwrap_or_else(|| 0.);
+    pv_generic(rate, nper, pmt, fv, pmt_at_begining)
+}
 
-    if rate == 0.0 {
+/// Calculates the present value like `pv`, for any floating point type.
+///
+/// # Examples
+///
+/// ```
+/// let pv = financial::generic::pv(0.1_f32, 5.0, Some(100.0), Some(1000.0), Some(false));
+/// assert!((pv - -1000.).abs() < 1e-3);
+/// ```
+pub fn pv_generic<T: Float>(
+    rate: T,
+    nper: T,
+    pmt: Option<T>,
+    fv: Option<T>,
+    pmt_at_begining: Option<bool>,
+) -> T {
+    let one = T::one();
+    let pmt = pmt.unwrap_or_else(T::zero);
+    let fv = fv.unwrap_or_else(T::zero);
+
+    if rate.is_zero() {
         -(fv + pmt * nper)
     } else {
-        let pmt_at_begining = if pmt_at_begining.unwrap_or_else(|| false) {
-            1.
+        let pmt_at_begining = if pmt_at_begining.unwrap_or(false) {
+            one
         } else {
-            0.
+            T::zero()
         };
-        let temp = f64::powf(1. + rate, nper);
-        let factor = (1. + rate * pmt_at_begining) * (temp - 1.) / rate;
+        let temp = (one + rate).powf(nper);
+        let factor = (one + rate * pmt_at_begining) * (temp - one) / rate;
         -(fv + pmt * factor) / temp
     }
 }
@@ -48,7 +69,7 @@ mod tests {
     fn pv_works_when_pmt_at_beginning() {
         assert_eq!(
             pv(0.1, 5.0, Some(100.0), Some(1000.0), Some(true)),
-            -1037.90786769408449
+            -1037.9078676940845
         );
     }
 
@@ -60,6 +81,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pv_generic_works_with_f32() {
+        let pv = pv_generic(0.1_f32, 5.0, Some(100.0), None, Some(false));
+        assert!(
+            (f64::from(pv) - -379.07867694084507).abs() < 1e-3,
+            "got {}",
+            pv
+        );
+        assert_eq!(pv_generic(0.0_f32, 5.0, Some(100.0), None, None), -500.0);
+    }
+
     #[test]
     fn pv_works_with_zero_rate() {
         assert_eq!(pv(0.0, 5.0, Some(100.0), Some(1000.0), None), -1500.0);
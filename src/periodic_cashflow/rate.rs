@@ -0,0 +1,120 @@
+use crate::common::find_root::find_root;
+use crate::error::FinancialError;
+use crate::periodic_cashflow::fv::fv;
+use crate::solver::SolverOptions;
+
+/// Returns the interest rate per period of an annuity.
+/// RATE is calculated by iteration and can have zero or more solutions.
+///
+/// # Examples
+///
+/// ```
+/// let rate = financial::rate(48.0, Some(-200.0), Some(8000.0), None, None, None).unwrap();
+/// assert!((rate - 0.007701472).abs() < 1e-7);
+/// ```
+pub fn rate(
+    nper: f64,
+    pmt: Option<f64>,
+    pv: Option<f64>,
+    fv: Option<f64>,
+    pmt_at_begining: Option<bool>,
+    guess: Option<f64>,
+) -> Result<f64, FinancialError> {
+    rate_with_options(
+        nper,
+        pmt,
+        pv,
+        fv,
+        pmt_at_begining,
+        guess,
+        &SolverOptions::default(),
+    )
+}
+
+/// Returns the interest rate per period of an annuity like `rate`, with the solver tuned by `options`.
+///
+/// # Examples
+///
+/// ```
+/// let options = financial::SolverOptions {
+///     f_tolerance: 1e-12,
+///     ..Default::default()
+/// };
+/// let rate = financial::rate_with_options(48.0, Some(-200.0), Some(8000.0), None, None, None, &options).unwrap();
+/// assert!((rate - 0.007701472).abs() < 1e-9);
+/// ```
+pub fn rate_with_options(
+    nper: f64,
+    pmt: Option<f64>,
+    pv: Option<f64>,
+    fv: Option<f64>,
+    pmt_at_begining: Option<bool>,
+    guess: Option<f64>,
+    options: &SolverOptions,
+) -> Result<f64, FinancialError> {
+    options.validate()?;
+
+    let target = fv.unwrap_or(0.);
+
+    // the rate we're looking for makes the future value of pv and pmt cancel out the target fv.
+    // The residual is discounted back to present value terms, otherwise it grows as (1 + x)^nper
+    // and the solver precision can't be reached for long annuities.
+    let f_fv =
+        |x: f64| (self::fv(x, nper, pmt, pv, pmt_at_begining) - target) / f64::powf(1. + x, nper);
+
+    // fv has a closed form at zero rate, which the solver can't resolve through the noise around it
+    if f_fv(0.).abs() <= options.f_tolerance && options.min_rate <= 0. && 0. <= options.max_rate {
+        return Ok(0.);
+    }
+
+    find_root(guess.or(Some(0.1)), f_fv, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+
+    #[test]
+    fn rate_works() {
+        let rate = rate(48.0, Some(-200.0), Some(8000.0), None, None, None).unwrap();
+        assert!((rate - 0.0077014724882).abs() <= PRECISION, "got {}", rate);
+    }
+
+    #[test]
+    fn rate_works_when_pmt_at_beginning() {
+        let rate = rate(
+            5.0,
+            Some(100.0),
+            Some(1000.0),
+            Some(-2282.071000000001),
+            Some(true),
+            None,
+        )
+        .unwrap();
+        assert!((rate - 0.1).abs() <= PRECISION, "got {}", rate);
+    }
+
+    #[test]
+    fn rate_works_with_zero_rate() {
+        let rate = rate(5.0, Some(100.0), Some(1000.0), Some(-1500.0), None, None).unwrap();
+        assert!(rate.abs() <= PRECISION, "got {}", rate);
+    }
+
+    #[test]
+    fn rate_fails_when_no_rate_exists() {
+        assert!(rate(5.0, Some(100.0), Some(1000.0), Some(1000.0), None, None).is_err());
+    }
+
+    #[test]
+    fn rate_with_options_rejects_invalid_options() {
+        let options = SolverOptions {
+            expansion_factor: 0.,
+            ..Default::default()
+        };
+        assert!(
+            rate_with_options(48.0, Some(-200.0), Some(8000.0), None, None, None, &options)
+                .is_err()
+        );
+    }
+}
@@ -1,5 +1,8 @@
 pub mod checked_cashflow_schedule;
+pub mod xfv;
 pub mod xirr;
+pub mod xirr_all;
 pub mod xnpv;
+pub mod xnpv_at;
 
 pub use crate::scheduled_cashflow::checked_cashflow_schedule::CheckedCashflowSchedule;
@@ -1,33 +1,61 @@
+use crate::error::FinancialError;
 use chrono::{DateTime, TimeZone};
+use num_traits::Float;
 
-pub struct CheckedCashflowSchedule<'a, T>
+pub struct CheckedCashflowSchedule<'a, T, V = f64>
 where
     T: TimeZone,
 {
-    pub values: &'a [f64],
+    pub values: &'a [V],
     pub dates: &'a [DateTime<T>],
 }
 
-impl<'a, T> CheckedCashflowSchedule<'a, T>
+impl<'a, T, V> CheckedCashflowSchedule<'a, T, V>
 where
     T: TimeZone,
+    V: Float,
 {
     pub fn new(
-        values: &'a [f64],
+        values: &'a [V],
         dates: &'a [DateTime<T>],
-    ) -> Result<CheckedCashflowSchedule<'a, T>, &'static str>
+    ) -> Result<CheckedCashflowSchedule<'a, T, V>, FinancialError>
     where
         T: TimeZone,
     {
-        if values.len() != dates.len() {
-            return Err("Values and dates length must match");
-        }
+        let cf = CheckedCashflowSchedule::new_in_any_order(values, dates)?;
 
-        let d0 = dates.first().unwrap();
+        let d0 = &dates[0];
         if dates.iter().any(|d| *d < *d0) {
-            return Err("First date must be the earliest");
+            return Err(FinancialError::UnsortedDates);
         };
 
+        Ok(cf)
+    }
+
+    /// Checks the schedule like `new`, except that dates can be in any order.
+    pub fn new_in_any_order(
+        values: &'a [V],
+        dates: &'a [DateTime<T>],
+    ) -> Result<CheckedCashflowSchedule<'a, T, V>, FinancialError> {
+        if values.len() != dates.len() {
+            return Err(FinancialError::LengthMismatch {
+                values: values.len(),
+                dates: dates.len(),
+            });
+        }
+        if values.is_empty() {
+            return Err(FinancialError::InvalidArgument {
+                name: "values",
+                reason: "must not be empty",
+            });
+        }
+        if values.iter().any(|v| !v.is_finite()) {
+            return Err(FinancialError::InvalidArgument {
+                name: "values",
+                reason: "must be finite",
+            });
+        }
+
         Ok(CheckedCashflowSchedule { values, dates })
     }
 }
@@ -0,0 +1,53 @@
+use crate::error::FinancialError;
+use crate::scheduled_cashflow::xnpv_at::xnpv_at;
+use chrono::{DateTime, TimeZone};
+
+/// Returns the future value at `horizon_date` of a schedule of cash flows that is not necessarily periodic.
+///
+/// Cash flows before `horizon_date` are compounded to it, over actual days / 365 like `xnpv`, and cash flows
+/// after it are discounted to it. The dates can be in any order.
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// let cf = [-500., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+/// ];
+///
+/// let xnpv = financial::xnpv(0.1, &cf, &dates).unwrap();
+/// let xfv = financial::xfv(0.1, &cf, &dates, dates[5]).unwrap();
+/// assert!((xfv - xnpv * 1.1_f64.powf(1826. / 365.)).abs() < 1e-9);
+/// ```
+pub fn xfv<T: TimeZone>(
+    rate: f64,
+    values: &[f64],
+    dates: &[DateTime<T>],
+    horizon_date: DateTime<T>,
+) -> Result<f64, FinancialError> {
+    xnpv_at(rate, values, dates, horizon_date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+    use chrono::Utc;
+
+    #[test]
+    fn xfv_of_single_flow_compounds_it() {
+        let dates = [
+            Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+        ];
+        let horizon = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+
+        let xfv = xfv(0.05, &[100., 0.], &dates, horizon).unwrap();
+        assert!((xfv - 100. * 1.05_f64.powf(731. / 365.)).abs() < PRECISION);
+    }
+}
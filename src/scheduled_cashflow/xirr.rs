@@ -1,21 +1,27 @@
-use crate::common::find_root::find_root;
-use crate::scheduled_cashflow::xnpv::calculate_xnpv;
+use crate::common::{find_root::solve, utils};
+use crate::day_count::DayCount;
+use crate::error::FinancialError;
+use crate::scheduled_cashflow::xnpv::{
+    discount, discount_derivative, year_fractions, year_fractions_with,
+};
 use crate::scheduled_cashflow::CheckedCashflowSchedule;
+use crate::solver::{SolveReport, SolverOptions};
 use chrono::{DateTime, TimeZone};
+use num_traits::Float;
 
 /// Returns the internal rate of return for a schedule of cash flows that is not necessarily periodic
 ///
 /// # Example
 /// ```
-/// use chrono::{NaiveDate, DateTime, Utc};
+/// use chrono::{TimeZone, Utc};
 /// let cf = [-379., 100., 100., 100., 100., 100.];
 /// let dates = [
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2016, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2017, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2018, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2019, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2020, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
 /// ];
 /// assert!((financial::xirr(&cf, &dates, None).unwrap() - 0.10004608364).abs() < 1e-7);
 /// ```
@@ -23,36 +29,192 @@ pub fn xirr<T: TimeZone>(
     values: &[f64],
     dates: &[DateTime<T>],
     guess: Option<f64>,
-) -> Result<f64, &'static str> {
-    let cf = CheckedCashflowSchedule::new(values, dates);
-
-    match cf {
-        Err(m) => Err(m),
-        Ok(cf) => {
-            let f_xnpv = |x: f64| calculate_xnpv(x, &cf);
-            match find_root(guess, f_xnpv, 1.1) {
-                Some(ans) => Ok(ans),
-                None => Err("could't find irr for the values provided"),
-            }
-        }
-    }
+) -> Result<f64, FinancialError> {
+    xirr_generic(values, dates, guess)
+}
+
+/// Returns the internal rate of return like `xirr`, for any floating point type.
+///
+/// For types less precise than `f64`, the solver accepts a root once XNPV is within the rounding error of `values`.
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// let cf = [-379_f32, 100., 100., 100., 100., 100.];
+/// let dates = [
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+/// ];
+/// assert!((financial::generic::xirr(&cf, &dates, None).unwrap() - 0.10004608).abs() < 1e-5);
+/// ```
+pub fn xirr_generic<V: Float, T: TimeZone>(
+    values: &[V],
+    dates: &[DateTime<T>],
+    guess: Option<V>,
+) -> Result<V, FinancialError> {
+    xirr_generic_with_options(values, dates, guess, &SolverOptions::default_for(values))
+}
+
+/// Returns the internal rate of return like `xirr_with_options`, for any floating point type.
+///
+/// The solver keeps its estimates in `f64`, like the rates and tolerances of `options`, and evaluates XNPV in `V`.
+/// With `f32`, `options.f_tolerance` must be loosened to what XNPV can resolve, which grows with the cashflows.
+pub fn xirr_generic_with_options<V: Float, T: TimeZone>(
+    values: &[V],
+    dates: &[DateTime<T>],
+    guess: Option<V>,
+    options: &SolverOptions,
+) -> Result<V, FinancialError> {
+    let cf = CheckedCashflowSchedule::new(values, dates)?;
+
+    solve_xirr(
+        cf.values,
+        &year_fractions(&cf),
+        guess.map(utils::cast),
+        options,
+        false,
+    )?
+    .into_result()
+    .map(utils::cast)
+}
+
+/// Returns the internal rate of return like `xirr`, with the solver tuned by `options`.
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// let cf = [-379., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+/// ];
+/// let options = financial::SolverOptions {
+///     f_tolerance: 1e-10,
+///     ..Default::default()
+/// };
+/// let rate = financial::xirr_with_options(&cf, &dates, None, &options).unwrap();
+/// assert!(financial::xnpv(rate, &cf, &dates).unwrap().abs() <= 1e-10);
+/// ```
+pub fn xirr_with_options<T: TimeZone>(
+    values: &[f64],
+    dates: &[DateTime<T>],
+    guess: Option<f64>,
+    options: &SolverOptions,
+) -> Result<f64, FinancialError> {
+    xirr_with_report(values, dates, guess, options, false)?.into_result()
+}
+
+/// Runs the solver of `xirr_with_options`, and reports how it went.
+///
+/// Invalid arguments are still returned as an error, but a solver that didn't converge returns a report without a root.
+/// Every estimate is recorded in `SolveReport::iterates` when `trace` is set.
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// let cf = [-379., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+/// ];
+/// let options = financial::SolverOptions::default();
+/// let report = financial::xirr_with_report(&cf, &dates, None, &options, true).unwrap();
+/// assert_eq!(report.phase, financial::SolvePhase::Newton);
+/// assert_eq!(report.iterates.len() as u32, report.newton_iterations);
+/// assert!((report.root.unwrap() - 0.10004608364).abs() < 1e-7);
+/// ```
+pub fn xirr_with_report<T: TimeZone>(
+    values: &[f64],
+    dates: &[DateTime<T>],
+    guess: Option<f64>,
+    options: &SolverOptions,
+    trace: bool,
+) -> Result<SolveReport, FinancialError> {
+    let cf = CheckedCashflowSchedule::new(values, dates)?;
+
+    solve_xirr(cf.values, &year_fractions(&cf), guess, options, trace)
+}
+
+/// Returns the internal rate of return for a schedule of cash flows that is not necessarily periodic,
+/// measuring the time between dates with `day_count` instead of actual days over 365.
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use financial::DayCount;
+/// let cf = [-379., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+/// ];
+/// let xirr = financial::xirr_with(&cf, &dates, None, &DayCount::Thirty360).unwrap();
+/// assert!((xirr - financial::irr(&cf, None).unwrap()).abs() < 1e-7);
+/// ```
+pub fn xirr_with<T: TimeZone>(
+    values: &[f64],
+    dates: &[DateTime<T>],
+    guess: Option<f64>,
+    day_count: &DayCount,
+) -> Result<f64, FinancialError> {
+    let cf = CheckedCashflowSchedule::new(values, dates)?;
+    let year_fractions = year_fractions_with(&cf, day_count);
+
+    solve_xirr(
+        cf.values,
+        &year_fractions,
+        guess,
+        &SolverOptions::default(),
+        false,
+    )?
+    .into_result()
+}
+
+fn solve_xirr<V: Float>(
+    values: &[V],
+    year_fractions: &[f64],
+    guess: Option<f64>,
+    options: &SolverOptions,
+    trace: bool,
+) -> Result<SolveReport, FinancialError> {
+    utils::validate_cashflow_values(values)?;
+
+    let f_xnpv = |x: f64| utils::cast::<f64, V>(discount(utils::cast(x), values, year_fractions));
+    let df_xnpv =
+        |x: f64| utils::cast::<f64, V>(discount_derivative(utils::cast(x), values, year_fractions));
+    solve(guess, f_xnpv, df_xnpv, options, trace)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{DateTime, NaiveDate, Utc};
+    use chrono::Utc;
 
     #[test]
     fn xirr_test() {
         let cf = [-500., 100., 100., 100., 100., 100.];
         let dates = [
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2016, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2017, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2018, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2019, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2020, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
         ];
         assert_eq!(xirr(&cf, &dates, None).unwrap(), 0.);
     }
@@ -61,15 +223,89 @@ mod tests {
     fn xirr_test2() {
         let cf = [-379., 100., 100., 100., 100., 100.];
         let dates = [
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2016, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2017, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2018, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2019, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2020, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
         ];
         assert!(
             (xirr(&cf, &dates, None).unwrap() - 0.10004608364551086) < crate::common::PRECISION
         );
     }
+
+    #[test]
+    fn xirr_fails_on_invalid_values() {
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+        ];
+
+        assert!(xirr::<Utc>(&[], &[], None).is_err());
+        assert_eq!(
+            xirr(&[0., 0.], &dates, None),
+            Err(FinancialError::NoSignChange)
+        );
+        assert!(xirr(&[-100., f64::NAN], &dates, None).is_err());
+        assert!(xirr(&[-100., f64::NEG_INFINITY], &dates, None).is_err());
+        assert!(xirr(&[-100., 110.], &dates, Some(f64::INFINITY)).is_err());
+    }
+
+    #[test]
+    fn xirr_generic_works_with_f32() {
+        let cf = [-1000., 250., 250., 250., 400.];
+        let cf_f32: Vec<f32> = cf.iter().map(|v| *v as f32).collect();
+        let dates = [
+            Utc.with_ymd_and_hms(2020, 1, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 2, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 8, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 2, 28, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 12, 31, 0, 0, 0).unwrap(),
+        ];
+
+        let xirr_f32 = xirr_generic(&cf_f32, &dates, None).unwrap();
+        let xirr = xirr(&cf, &dates, None).unwrap();
+        assert!(
+            (f64::from(xirr_f32) - xirr).abs() <= 1e-5,
+            "got {}, expected {}",
+            xirr_f32,
+            xirr
+        );
+        assert!(xirr_generic(&cf_f32[..2], &dates, None).is_err());
+    }
+
+    #[test]
+    fn xirr_with_zeroes_xnpv_with() {
+        let cf = [-1000., 250., 250., 250., 400.];
+        let dates = [
+            Utc.with_ymd_and_hms(2020, 1, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 2, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 8, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 2, 28, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 12, 31, 0, 0, 0).unwrap(),
+        ];
+
+        for day_count in [
+            DayCount::Act365Fixed,
+            DayCount::Act360,
+            DayCount::ActActIsda,
+            DayCount::ActActIcma(crate::frequency::Frequency::Monthly),
+            DayCount::Thirty360Us,
+            DayCount::ThirtyE360,
+            DayCount::ThirtyE360Isda,
+            DayCount::Act365L,
+        ]
+        .iter()
+        {
+            let rate = xirr_with(&cf, &dates, None, day_count).unwrap();
+            let xnpv = crate::scheduled_cashflow::xnpv::xnpv_with(rate, &cf, &dates, day_count);
+            assert!(xnpv.unwrap().abs() < 1e-7, "{:?}", day_count);
+        }
+
+        assert_eq!(
+            xirr_with(&cf, &dates, None, &DayCount::Act365Fixed),
+            xirr(&cf, &dates, None)
+        );
+    }
 }
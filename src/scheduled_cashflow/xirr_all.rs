@@ -0,0 +1,99 @@
+use crate::common::utils;
+use crate::error::FinancialError;
+use crate::periodic_cashflow::irr_all::{rates_in_range, IrrRoots};
+use crate::scheduled_cashflow::xnpv::year_fractions;
+use crate::scheduled_cashflow::CheckedCashflowSchedule;
+use chrono::{DateTime, TimeZone};
+
+/// Returns all the internal rates of return within `range`, bounds included, for a schedule of cash flows
+/// that is not necessarily periodic.
+///
+/// Rates below -100% are never returned, since discounting at those rates isn't defined for fractions of a year.
+/// As with `irr_all`, the cost of root isolation grows quadratically with the number of cash flows.
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// let cf = [-1000., 3600., -4310., 1716.];
+/// let dates = [
+///     Utc.with_ymd_and_hms(2015, 1, 1, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2016, 12, 31, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 12, 31, 0, 0, 0).unwrap(),
+/// ];
+/// let irr = financial::xirr_all(&cf, &dates, (-0.99, 1.)).unwrap();
+/// assert_eq!(irr.roots.len(), 3);
+/// assert!(!irr.single_sign_change);
+/// assert!(!irr.is_unique());
+/// ```
+pub fn xirr_all<T: TimeZone>(
+    values: &[f64],
+    dates: &[DateTime<T>],
+    range: (f64, f64),
+) -> Result<IrrRoots, FinancialError> {
+    let cf = CheckedCashflowSchedule::new(values, dates)?;
+
+    utils::validate_cashflow_values(cf.values)?;
+
+    // xnpv is a polynomial of the discount factor 1 / (1 + rate) with fractional exponents
+    let terms: Vec<(f64, f64)> = cf.values.iter().cloned().zip(year_fractions(&cf)).collect();
+
+    rates_in_range(&terms, range, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+    use crate::scheduled_cashflow::xirr::xirr;
+    use crate::scheduled_cashflow::xnpv::xnpv;
+    use chrono::Utc;
+
+    #[test]
+    fn xirr_all_finds_every_root() {
+        let cf = [-1000., 3600., -4310., 1716.];
+        let dates = [
+            Utc.with_ymd_and_hms(2015, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2016, 12, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 12, 31, 0, 0, 0).unwrap(),
+        ];
+        let irr = xirr_all(&cf, &dates, (-1., f64::INFINITY)).unwrap();
+
+        assert_eq!(irr.roots.len(), 3, "got {:?}", irr.roots);
+        for (root, expected) in irr.roots.iter().zip([0.1, 0.2, 0.3].iter()) {
+            assert!((root - expected).abs() <= PRECISION, "got {:?}", irr.roots);
+            assert!(xnpv(*root, &cf, &dates).unwrap().abs() <= PRECISION);
+        }
+    }
+
+    #[test]
+    fn xirr_all_agrees_with_xirr() {
+        let cf = [-379., 100., 100., 100., 100., 100.];
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 3, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 3, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2019, 1, 15, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 2, 1, 0, 0, 0).unwrap(),
+        ];
+        let irr = xirr_all(&cf, &dates, (f64::NEG_INFINITY, f64::INFINITY)).unwrap();
+
+        assert!(irr.single_sign_change);
+        assert_eq!(irr.roots.len(), 1);
+        assert!((irr.roots[0] - xirr(&cf, &dates, None).unwrap()).abs() <= PRECISION);
+    }
+
+    #[test]
+    fn xirr_all_fails_on_invalid_arguments() {
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+        ];
+
+        assert!(xirr_all(&[-100.], &dates, (0., 1.)).is_err());
+        assert!(xirr_all(&[100., 100.], &dates, (0., 1.)).is_err());
+        assert!(xirr_all(&[-100., 110.], &dates, (1., 0.)).is_err());
+    }
+}
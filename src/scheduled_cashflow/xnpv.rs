@@ -1,20 +1,23 @@
 use crate::common::utils;
+use crate::day_count::DayCount;
+use crate::error::FinancialError;
 use crate::scheduled_cashflow::CheckedCashflowSchedule;
 use chrono::{offset::TimeZone, DateTime};
+use num_traits::Float;
 
 /// Returns the net present value for a schedule of cash flows that is not necessarily periodic.
 ///
 /// # Example
 /// ```
-/// use chrono::{NaiveDate, DateTime, Utc, Duration};
+/// use chrono::{TimeZone, Utc};
 /// let cf = [-500., 100., 100., 100., 100., 100.];
 /// let dates = [
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2016, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2017, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2018, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2019, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2020, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-///     DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
 /// ];
 /// assert_eq!(financial::xnpv(0.1, &cf, &dates).unwrap(), -120.9553674519204);    
 /// ```
@@ -22,35 +25,146 @@ pub fn xnpv<T: TimeZone>(
     rate: f64,
     values: &[f64],
     dates: &[DateTime<T>],
-) -> Result<f64, &'static str> {
-    let cf = CheckedCashflowSchedule::new(values, dates);
-    match cf {
-        Err(m) => Err(m),
-        Ok(cf) => Ok(calculate_xnpv(rate, &cf)),
+) -> Result<f64, FinancialError> {
+    xnpv_generic(rate, values, dates)
+}
+
+/// Returns the net present value like `xnpv`, for any floating point type.
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// let cf = [-500_f32, 100., 100., 100., 100., 100.];
+/// let dates = [
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+/// ];
+/// let xnpv = financial::generic::xnpv(0.1, &cf, &dates).unwrap();
+/// assert!((xnpv - -120.95537).abs() < 1e-3);
+/// ```
+pub fn xnpv_generic<V: Float, T: TimeZone>(
+    rate: V,
+    values: &[V],
+    dates: &[DateTime<T>],
+) -> Result<V, FinancialError> {
+    if !rate.is_finite() || rate <= -V::one() {
+        return Err(FinancialError::InvalidArgument {
+            name: "rate",
+            reason: "must be finite and greater than -1",
+        });
+    }
+
+    let cf = CheckedCashflowSchedule::new(values, dates)?;
+
+    Ok(calculate_xnpv(rate, &cf))
+}
+
+/// Returns the net present value for a schedule of cash flows that is not necessarily periodic,
+/// measuring the time between dates with `day_count` instead of actual days over 365.
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use financial::DayCount;
+/// let cf = [-500., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+/// ];
+/// let npv = financial::npv(0.1, &cf[1..]) + cf[0];
+/// let xnpv = financial::xnpv_with(0.1, &cf, &dates, &DayCount::Thirty360).unwrap();
+/// assert!((xnpv - npv).abs() < 1e-9);
+/// ```
+pub fn xnpv_with<T: TimeZone>(
+    rate: f64,
+    values: &[f64],
+    dates: &[DateTime<T>],
+    day_count: &DayCount,
+) -> Result<f64, FinancialError> {
+    if !rate.is_finite() || rate <= -1. {
+        return Err(FinancialError::InvalidArgument {
+            name: "rate",
+            reason: "must be finite and greater than -1",
+        });
     }
+
+    let cf = CheckedCashflowSchedule::new(values, dates)?;
+    let year_fractions = year_fractions_with(&cf, day_count);
+
+    Ok(discount(rate, cf.values, &year_fractions))
 }
 
-pub fn calculate_xnpv<T: TimeZone>(rate: f64, cf: &CheckedCashflowSchedule<T>) -> f64 {
-    if cf.values.is_empty() {
-        return 0.;
+pub fn calculate_xnpv<T: TimeZone, V: Float>(rate: V, cf: &CheckedCashflowSchedule<T, V>) -> V {
+    discount(rate, cf.values, &year_fractions(cf))
+}
+
+/// Years from the first date to each date, as Excel counts them: actual days over 365.
+pub fn year_fractions<T: TimeZone, V>(cf: &CheckedCashflowSchedule<T, V>) -> Vec<f64> {
+    match cf.dates.first() {
+        Some(d0) => cf
+            .dates
+            .iter()
+            .map(|d| utils::days_to(d0.clone(), d.clone()) / 365.)
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Years from the first date to each date according to `day_count`.
+pub fn year_fractions_with<T: TimeZone, V>(
+    cf: &CheckedCashflowSchedule<T, V>,
+    day_count: &DayCount,
+) -> Vec<f64> {
+    match cf.dates.first() {
+        Some(d0) => {
+            let d0 = d0.naive_local().date();
+            cf.dates
+                .iter()
+                .map(|d| day_count.year_fraction(d0, d.naive_local().date()))
+                .collect()
+        }
+        None => vec![],
     }
+}
 
-    if rate == 0. {
-        return cf.values.iter().sum();
+/// Discounts each value by `rate` over its year fraction.
+pub fn discount<V: Float>(rate: V, values: &[V], year_fractions: &[f64]) -> V {
+    if rate.is_zero() {
+        return values.iter().fold(V::zero(), |sum, v| sum + *v);
     }
 
-    let d0 = cf.dates.first().unwrap();
-    cf.values
+    let base = V::one() + rate;
+    values
+        .iter()
+        .zip(year_fractions.iter())
+        .fold(V::zero(), |sum, (v, t)| {
+            sum + *v / base.powf(utils::cast(*t))
+        })
+}
+
+/// Derivative of `discount` with respect to `rate`.
+pub fn discount_derivative<V: Float>(rate: V, values: &[V], year_fractions: &[f64]) -> V {
+    let base = V::one() + rate;
+    values
         .iter()
-        .zip(cf.dates.iter())
-        .map(|(v, d)| v / f64::powf(1. + rate, utils::days_to(d0.clone(), d.clone()) / 365.))
-        .sum()
+        .zip(year_fractions.iter())
+        .fold(V::zero(), |sum, (v, t)| {
+            sum + utils::cast::<V, f64>(-t) * *v / base.powf(utils::cast(t + 1.))
+        })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{DateTime, Duration, NaiveDate, Utc};
+    use chrono::{DateTime, Duration, Utc};
 
     #[test]
     fn xnpv_with_zero_rate() {
@@ -58,12 +172,10 @@ mod tests {
         let dates0: [DateTime<Utc>; 10000] = [Utc::now(); 10000];
         let mut dates: [DateTime<Utc>; 10000] = [Utc::now(); 10000];
 
-        let mut i = 0;
-        for d in dates0.iter() {
+        for (i, d) in dates0.iter().enumerate() {
             dates[i] = d
                 .checked_add_signed(Duration::weeks(52 * (i as i64)))
                 .unwrap();
-            i = i + 1;
         }
 
         assert_eq!(xnpv(0., &cf, &dates).unwrap(), cf.iter().sum());
@@ -73,13 +185,120 @@ mod tests {
     fn xnpv_test() {
         let cf = [-500., 100., 100., 100., 100., 100.];
         let dates = [
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2016, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2017, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2018, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2019, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2020, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
-            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 7, 8).and_hms_opt(0, 0, 0).unwrap(), Utc),
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
         ];
         assert_eq!(xnpv(0.1, &cf, &dates).unwrap(), -120.9553674519204);
     }
+
+    #[test]
+    fn xnpv_fails_with_invalid_schedule() {
+        let cf = [-500., 100., 100.];
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2015, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+        ];
+
+        assert_eq!(xnpv(0.1, &cf, &dates), Err(FinancialError::UnsortedDates));
+        assert_eq!(
+            xnpv(0.1, &cf[..2], &dates),
+            Err(FinancialError::LengthMismatch {
+                values: 2,
+                dates: 3
+            })
+        );
+    }
+
+    #[test]
+    fn xnpv_fails_on_invalid_values() {
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+        ];
+
+        assert!(xnpv::<Utc>(0.1, &[], &[]).is_err());
+        assert!(xnpv(0.1, &[-100., f64::NAN], &dates).is_err());
+        assert!(xnpv(0.1, &[-100., f64::INFINITY], &dates).is_err());
+        assert!(xnpv(f64::NAN, &[-100., 110.], &dates).is_err());
+        assert!(xnpv(-1., &[-100., 110.], &dates).is_err());
+        assert_eq!(xnpv(0.1, &[0., 0.], &dates), Ok(0.));
+    }
+
+    #[test]
+    fn xnpv_generic_works_with_f32() {
+        let cf = [-500., 100., 100., 100., 100., 100.];
+        let cf_f32: Vec<f32> = cf.iter().map(|v| *v as f32).collect();
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 1, 9, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2018, 2, 28, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2019, 7, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 2, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+        ];
+
+        let xnpv_f32 = xnpv_generic(0.1_f32, &cf_f32, &dates).unwrap();
+        let xnpv = xnpv(0.1, &cf, &dates).unwrap();
+        assert!(
+            (f64::from(xnpv_f32) - xnpv).abs() <= 1e-4,
+            "got {}, expected {}",
+            xnpv_f32,
+            xnpv
+        );
+        assert!(xnpv_generic(-1_f32, &cf_f32, &dates).is_err());
+        assert!(xnpv_generic(0.1, &[-100_f32, f32::NAN], &dates[..2]).is_err());
+    }
+
+    #[test]
+    fn discount_derivative_matches_finite_difference() {
+        let values = [-1000., 300., 400., 500.];
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2016, 4, 15, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 2, 28, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+        ];
+        let cf = CheckedCashflowSchedule::new(&values, &dates).unwrap();
+        let h = 1e-6;
+        for rate in [-0.5, 0., 0.1, 2.].iter() {
+            let expected =
+                (calculate_xnpv(rate + h, &cf) - calculate_xnpv(rate - h, &cf)) / (2. * h);
+            let derivative = discount_derivative(*rate, cf.values, &year_fractions(&cf));
+            assert!(
+                (derivative - expected).abs() <= 1e-4 * expected.abs(),
+                "rate {}: got {}, expected {}",
+                rate,
+                derivative,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn xnpv_with_act_365_fixed_matches_xnpv() {
+        let cf = [-500., 100., 100., 100., 100., 100.];
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 1, 9, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2018, 2, 28, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2019, 7, 31, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 2, 29, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+        ];
+
+        assert_eq!(
+            xnpv_with(0.1, &cf, &dates, &DayCount::Act365Fixed),
+            xnpv(0.1, &cf, &dates)
+        );
+        assert_ne!(
+            xnpv_with(0.1, &cf, &dates, &DayCount::Act360),
+            xnpv(0.1, &cf, &dates)
+        );
+        assert!(xnpv_with(-1., &cf, &dates, &DayCount::Act360).is_err());
+    }
 }
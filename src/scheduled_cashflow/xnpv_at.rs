@@ -0,0 +1,113 @@
+use crate::common::utils;
+use crate::error::FinancialError;
+use crate::scheduled_cashflow::xnpv::discount;
+use crate::scheduled_cashflow::CheckedCashflowSchedule;
+use chrono::{DateTime, TimeZone};
+
+/// Returns the value at `valuation_date` of a schedule of cash flows that is not necessarily periodic.
+///
+/// Cash flows after `valuation_date` are discounted to it and cash flows before it are compounded to it,
+/// over actual days / 365 like `xnpv`. The dates can be in any order, and `valuation_date` can be before,
+/// inside or after them. This is `xnpv` when `valuation_date` is the first date.
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// let cf = [-500., 100., 100., 100., 100., 100.];
+/// let dates = [
+///     Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2019, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2021, 7, 8, 0, 0, 0).unwrap(),
+/// ];
+/// let reporting_date = Utc.with_ymd_and_hms(2018, 12, 31, 0, 0, 0).unwrap();
+///
+/// let xnpv = financial::xnpv(0.1, &cf, &dates).unwrap();
+/// let xnpv_at = financial::xnpv_at(0.1, &cf, &dates, reporting_date).unwrap();
+/// assert!((xnpv_at - xnpv * 1.1_f64.powf(906. / 365.)).abs() < 1e-9);
+/// ```
+pub fn xnpv_at<T: TimeZone>(
+    rate: f64,
+    values: &[f64],
+    dates: &[DateTime<T>],
+    valuation_date: DateTime<T>,
+) -> Result<f64, FinancialError> {
+    if !rate.is_finite() || rate <= -1. {
+        return Err(FinancialError::InvalidArgument {
+            name: "rate",
+            reason: "must be finite and greater than -1",
+        });
+    }
+
+    let cf = CheckedCashflowSchedule::new_in_any_order(values, dates)?;
+    let year_fractions: Vec<f64> = cf
+        .dates
+        .iter()
+        .map(|d| utils::days_to(valuation_date.clone(), d.clone()) / 365.)
+        .collect();
+
+    Ok(discount(rate, cf.values, &year_fractions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRECISION;
+    use crate::scheduled_cashflow::xnpv::xnpv;
+    use chrono::Utc;
+
+    #[test]
+    fn xnpv_at_first_date_is_xnpv() {
+        let cf = [-500., 100., 200., 300.];
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 3, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2019, 1, 8, 0, 0, 0).unwrap(),
+        ];
+
+        assert_eq!(
+            xnpv_at(0.1, &cf, &dates, dates[0]).unwrap(),
+            xnpv(0.1, &cf, &dates).unwrap()
+        );
+    }
+
+    #[test]
+    fn xnpv_at_accepts_any_order_and_date() {
+        let cf = [100., -500., 450.];
+        let dates = [
+            Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2018, 7, 8, 0, 0, 0).unwrap(),
+        ];
+        let sorted_cf = [-500., 100., 450.];
+        let sorted_dates = [dates[1], dates[0], dates[2]];
+        let xnpv = xnpv(0.1, &sorted_cf, &sorted_dates).unwrap();
+
+        let before = Utc.with_ymd_and_hms(2015, 7, 8, 0, 0, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2020, 7, 8, 0, 0, 0).unwrap();
+        assert!(
+            (xnpv_at(0.1, &cf, &dates, before).unwrap() - xnpv / 1.1_f64.powf(366. / 365.)).abs()
+                < PRECISION
+        );
+        assert!(
+            (xnpv_at(0.1, &cf, &dates, after).unwrap() - xnpv * 1.1_f64.powf(1461. / 365.)).abs()
+                < PRECISION
+        );
+    }
+
+    #[test]
+    fn xnpv_at_fails_on_invalid_arguments() {
+        let dates = [
+            Utc.with_ymd_and_hms(2016, 7, 8, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2017, 7, 8, 0, 0, 0).unwrap(),
+        ];
+
+        assert!(xnpv_at(0.1, &[], &[], dates[0]).is_err());
+        assert!(xnpv_at(0.1, &[-100.], &dates, dates[0]).is_err());
+        assert!(xnpv_at(0.1, &[-100., f64::NAN], &dates, dates[0]).is_err());
+        assert!(xnpv_at(-1., &[-100., 110.], &dates, dates[0]).is_err());
+    }
+}
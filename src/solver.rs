@@ -0,0 +1,227 @@
+use crate::common::{utils, PRECISION};
+use crate::error::FinancialError;
+use num_traits::Float;
+
+/// The algorithm used to find a root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverMethod {
+    /// Newton's method with an analytic derivative where one is known, falling back to Brent's method.
+    Hybrid,
+    /// Brent's method only, which always returns a root within the first bracket found around the guess.
+    Brent,
+    /// Newton's method with a finite difference derivative, falling back to bisection.
+    /// This is the solver used by earlier versions of this crate.
+    NewtonBisection,
+}
+
+/// Tuning knobs for the root finder behind `irr`, `xirr` and `rate`.
+///
+/// The solver first runs Newton's method from the guess. If that fails, it searches for a bracket around the guess,
+/// starting `initial_bracket` away on each side and widening it by `expansion_factor` on every attempt,
+/// and then narrows it down with the bracketed method picked by `method`.
+///
+/// # Example
+/// ```
+/// let options = financial::SolverOptions {
+///     f_tolerance: 1e-10,
+///     ..Default::default()
+/// };
+/// let cf = [-500., 100., 100., 100., 100.];
+/// let irr = financial::irr_with_options(&cf, None, &options).unwrap();
+/// assert!(financial::npv(irr, &cf).abs() <= 1e-10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverOptions {
+    /// The algorithm used to find the root, `SolverMethod::Hybrid` by default.
+    pub method: SolverMethod,
+    /// Newton's method stops once its step is no larger than this.
+    pub x_tolerance: f64,
+    /// A rate is accepted as a root once the absolute value of the function is no larger than this.
+    pub f_tolerance: f64,
+    /// Iterations allowed to Newton's method before falling back to a bracket search, 20 by default.
+    /// Ignored by `SolverMethod::Brent`.
+    pub newton_max_iterations: u32,
+    /// How many times the bracket around the guess may be widened while looking for a sign change,
+    /// 60 by default.
+    pub bracket_max_iterations: u32,
+    /// Iterations allowed to Brent's method or bisection once a bracket is found, 2000 by default.
+    pub bracketed_max_iterations: u32,
+    /// Distance from the guess to each side of the first bracket tried.
+    pub initial_bracket: f64,
+    /// How much the bracket grows on each side, relative to its width, when it doesn't contain a root.
+    pub expansion_factor: f64,
+    /// Lowest rate the solver is allowed to return.
+    pub min_rate: f64,
+    /// Highest rate the solver is allowed to return.
+    pub max_rate: f64,
+}
+
+impl Default for SolverOptions {
+    fn default() -> SolverOptions {
+        SolverOptions {
+            method: SolverMethod::Hybrid,
+            x_tolerance: PRECISION,
+            f_tolerance: PRECISION,
+            newton_max_iterations: 20,
+            bracket_max_iterations: 60,
+            bracketed_max_iterations: 2000,
+            initial_bracket: 0.01,
+            // we are interested in a root closest to the initial guess, so the search area is expanded conservatively
+            expansion_factor: 1.1,
+            min_rate: f64::MIN,
+            max_rate: f64::MAX,
+        }
+    }
+}
+
+/// The phase the solver ended in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolvePhase {
+    /// Newton's method found the root.
+    Newton,
+    /// Newton's method failed and no bracket around a root could be found.
+    BracketSearch,
+    /// Brent's method ran on the bracket found.
+    Brent,
+    /// Bisection ran on the bracket found.
+    Bisection,
+}
+
+/// How the solver went, whether it found a root or not.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveReport {
+    pub root: Option<f64>,
+    pub phase: SolvePhase,
+    pub newton_iterations: u32,
+    pub bracket_search_iterations: u32,
+    /// Iterations of Brent's method or bisection.
+    pub bracketed_iterations: u32,
+    /// The root if found, otherwise the estimate the solver stopped at.
+    pub last_estimate: f64,
+    /// The value of the function at `last_estimate`.
+    pub residual: f64,
+    /// The bracket found around a root after Newton's method failed.
+    pub bracket: Option<(f64, f64)>,
+    /// Every estimate in order, when the report was asked to trace them.
+    pub iterates: Vec<f64>,
+}
+
+impl SolveReport {
+    /// Returns the root, or the `DidNotConverge` error the solver functions return.
+    pub fn into_result(self) -> Result<f64, FinancialError> {
+        match self.root {
+            Some(root) => Ok(root),
+            None => Err(FinancialError::DidNotConverge {
+                iterations: self.newton_iterations + self.bracketed_iterations,
+                last_estimate: self.last_estimate,
+            }),
+        }
+    }
+}
+
+impl SolverOptions {
+    // The default options for a function of `values` evaluated in `T`. For types less precise than f64,
+    // `f_tolerance` is loosened to the rounding error of summing `values`, which is all `T` can resolve.
+    pub(crate) fn default_for<T: Float>(values: &[T]) -> SolverOptions {
+        let defaults = SolverOptions::default();
+        let epsilon: f64 = utils::cast(T::epsilon());
+        if epsilon <= f64::EPSILON {
+            return defaults;
+        }
+
+        let magnitude = values
+            .iter()
+            .fold(0., |sum, v| sum + utils::cast::<f64, T>(v.abs()));
+        SolverOptions {
+            f_tolerance: defaults.f_tolerance.max(4. * epsilon * magnitude),
+            ..defaults
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), FinancialError> {
+        let positive = |name, value: f64| {
+            if value.is_finite() && value > 0. {
+                Ok(())
+            } else {
+                Err(FinancialError::InvalidArgument {
+                    name,
+                    reason: "must be positive and finite",
+                })
+            }
+        };
+
+        positive("x_tolerance", self.x_tolerance)?;
+        positive("f_tolerance", self.f_tolerance)?;
+        positive("initial_bracket", self.initial_bracket)?;
+        positive("expansion_factor", self.expansion_factor)?;
+
+        if self.min_rate.is_nan() || self.max_rate.is_nan() || self.min_rate >= self.max_rate {
+            return Err(FinancialError::InvalidArgument {
+                name: "max_rate",
+                reason: "must be greater than min_rate",
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_options_are_valid() {
+        assert_eq!(SolverOptions::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn default_for_loosens_tolerance_below_f64_precision() {
+        let values = [-1000., 300., 400., 500.];
+        assert_eq!(
+            SolverOptions::default_for(&values),
+            SolverOptions::default()
+        );
+
+        let values = [-1000_f32, 300., 400., 500.];
+        let options = SolverOptions::default_for(&values);
+        assert!(options.f_tolerance > SolverOptions::default().f_tolerance);
+        assert!(options.f_tolerance < 1e-2);
+    }
+
+    #[test]
+    fn invalid_options_are_rejected() {
+        let defaults = SolverOptions::default();
+        let invalid = [
+            SolverOptions {
+                x_tolerance: 0.,
+                ..defaults
+            },
+            SolverOptions {
+                f_tolerance: f64::NAN,
+                ..defaults
+            },
+            SolverOptions {
+                initial_bracket: -0.01,
+                ..defaults
+            },
+            SolverOptions {
+                expansion_factor: f64::INFINITY,
+                ..defaults
+            },
+            SolverOptions {
+                min_rate: 0.5,
+                max_rate: 0.1,
+                ..defaults
+            },
+            SolverOptions {
+                min_rate: f64::NAN,
+                ..defaults
+            },
+        ];
+
+        for options in invalid.iter() {
+            assert!(options.validate().is_err(), "{:?}", options);
+        }
+    }
+}
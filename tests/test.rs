@@ -1,8 +1,8 @@
 #[cfg(test)]
 mod tests {
 
-    use chrono::{DateTime, NaiveDate, Utc};
-    use financial;
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+    use std::convert::TryFrom;
 
     pub const PRECISION: f64 = 1e-7;
 
@@ -34,15 +34,11 @@ mod tests {
             let mut test_case = test_case;
 
             let (ans, values): (f64, Vec<f64>) = (
-                test_case
-                    .next()
-                    .unwrap()
-                    .parse::<f64>()
-                    .unwrap_or_else(|_| f64::NAN),
+                test_case.next().unwrap().parse::<f64>().unwrap_or(f64::NAN),
                 test_case.map(|x| x.parse::<f64>().unwrap()).collect(),
             );
 
-            let res = financial::irr(&values, None).unwrap_or_else(|_| f64::NAN);
+            let res = financial::irr(&values, None).unwrap_or(f64::NAN);
 
             if !ans.is_nan() {
                 assert!(
@@ -60,7 +56,7 @@ mod tests {
     fn mirr() {
         test_fn("./tests/test_data/mirr.csv", |test_case, case_index| {
             let mut test_case = test_case;
-            let parse_next_f64_or = |x : Option<&str>| x.unwrap().parse::<f64>().unwrap_or_else(|_| f64::NAN); 
+            let parse_next_f64_or = |x: Option<&str>| x.unwrap().parse::<f64>().unwrap_or(f64::NAN);
             let (ans, finance_rate, reinvest_rate, values): (f64, f64, f64, Vec<f64>) = (
                 parse_next_f64_or(test_case.next()),
                 parse_next_f64_or(test_case.next()),
@@ -78,10 +74,152 @@ mod tests {
                     ans,
                     res
                 );
+            } else {
+                assert!(res.is_infinite());
             }
-            else {
-                assert_eq!(res.is_infinite(), true);
-            }
+        });
+    }
+
+    #[test]
+    fn pmt() {
+        test_fn("./tests/test_data/pmt.csv", |test_case, case_index| {
+            let mut test_case = test_case;
+
+            let ans = test_case.next().unwrap();
+            let values: Vec<f64> = test_case.map(|x| x.parse::<f64>().unwrap()).collect();
+            let (rate, nper, pv, fv, pmt_at_begining) =
+                (values[0], values[1], values[2], values[3], values[4] == 1.);
+
+            let res = financial::pmt(rate, nper, Some(pv), Some(fv), Some(pmt_at_begining));
+            assert_displayed_eq(ans, res, case_index);
+        });
+    }
+
+    #[test]
+    fn ipmt() {
+        test_fn("./tests/test_data/ipmt.csv", |test_case, case_index| {
+            let mut test_case = test_case;
+
+            let ans = test_case.next().unwrap();
+            let values: Vec<f64> = test_case.map(|x| x.parse::<f64>().unwrap()).collect();
+            let (rate, per, nper, pv, fv, pmt_at_begining) = (
+                values[0],
+                values[1],
+                values[2],
+                values[3],
+                values[4],
+                values[5] == 1.,
+            );
+
+            let res = financial::ipmt(rate, per, nper, Some(pv), Some(fv), Some(pmt_at_begining))
+                .unwrap();
+            assert_displayed_eq(ans, res, case_index);
+        });
+    }
+
+    #[test]
+    fn ppmt() {
+        test_fn("./tests/test_data/ppmt.csv", |test_case, case_index| {
+            let mut test_case = test_case;
+
+            let ans = test_case.next().unwrap();
+            let values: Vec<f64> = test_case.map(|x| x.parse::<f64>().unwrap()).collect();
+            let (rate, per, nper, pv, fv, pmt_at_begining) = (
+                values[0],
+                values[1],
+                values[2],
+                values[3],
+                values[4],
+                values[5] == 1.,
+            );
+
+            let res = financial::ppmt(rate, per, nper, Some(pv), Some(fv), Some(pmt_at_begining))
+                .unwrap();
+            assert_displayed_eq(ans, res, case_index);
+        });
+    }
+
+    #[test]
+    fn nper() {
+        test_fn("./tests/test_data/nper.csv", |test_case, case_index| {
+            let mut test_case = test_case;
+
+            let ans = test_case.next().unwrap();
+            let values: Vec<f64> = test_case.map(|x| x.parse::<f64>().unwrap()).collect();
+            let (rate, pmt, pv, fv, pmt_at_begining) =
+                (values[0], values[1], values[2], values[3], values[4] == 1.);
+
+            let res = financial::nper(rate, Some(pmt), Some(pv), Some(fv), Some(pmt_at_begining));
+
+            assert_displayed_eq(ans, res.unwrap(), case_index);
+        });
+    }
+
+    #[test]
+    fn rate() {
+        test_fn("./tests/test_data/rate.csv", |test_case, case_index| {
+            let mut test_case = test_case;
+
+            let ans = test_case.next().unwrap();
+            let values: Vec<f64> = test_case.map(|x| x.parse::<f64>().unwrap()).collect();
+            let (nper, pmt, pv, fv, pmt_at_begining) =
+                (values[0], values[1], values[2], values[3], values[4] == 1.);
+
+            let res = financial::rate(
+                nper,
+                Some(pmt),
+                Some(pv),
+                Some(fv),
+                Some(pmt_at_begining),
+                None,
+            )
+            .unwrap();
+            assert_displayed_eq(ans, res, case_index);
+        });
+    }
+
+    #[test]
+    fn cumipmt() {
+        test_fn("./tests/test_data/cumipmt.csv", |test_case, case_index| {
+            let mut test_case = test_case;
+
+            let ans = test_case.next().unwrap();
+            let values: Vec<f64> = test_case.map(|x| x.parse::<f64>().unwrap()).collect();
+            let (rate, nper, pv, start_period, end_period, pmt_at_begining) = (
+                values[0],
+                values[1],
+                values[2],
+                values[3],
+                values[4],
+                values[5] == 1.,
+            );
+
+            let res = financial::cumipmt(rate, nper, pv, start_period, end_period, pmt_at_begining)
+                .unwrap();
+            assert_displayed_eq(ans, res, case_index);
+        });
+    }
+
+    #[test]
+    fn cumprinc() {
+        test_fn("./tests/test_data/cumprinc.csv", |test_case, case_index| {
+            let mut test_case = test_case;
+
+            let ans = test_case.next().unwrap();
+            let values: Vec<f64> = test_case.map(|x| x.parse::<f64>().unwrap()).collect();
+            let (rate, nper, pv, start_period, end_period, pmt_at_begining) = (
+                values[0],
+                values[1],
+                values[2],
+                values[3],
+                values[4],
+                values[5] == 1.,
+            );
+
+            let res =
+                financial::cumprinc(rate, nper, pv, start_period, end_period, pmt_at_begining)
+                    .unwrap();
+            assert_displayed_eq(ans, res, case_index);
         });
     }
 
@@ -91,12 +229,11 @@ mod tests {
             let mut test_case = test_case;
 
             let date_from_str = |x| {
-                DateTime::<Utc>::from_utc(
-                    NaiveDate::parse_from_str(x, "%m/%d/%Y")
+                Utc.from_utc_datetime(
+                    &NaiveDate::parse_from_str(x, "%m/%d/%Y")
                         .unwrap()
                         .and_hms_opt(0, 0, 0)
                         .unwrap(),
-                    Utc,
                 )
             };
 
@@ -108,7 +245,7 @@ mod tests {
                     .take_while(|x| x.parse::<f64>().is_ok())
                     .map(|x| x.parse::<f64>().unwrap())
                     .collect(),
-                test_case.map(|x| date_from_str(x)).collect(),
+                test_case.map(date_from_str).collect(),
             );
 
             let res = financial::xnpv(r, &values, &dates).unwrap();
@@ -128,30 +265,25 @@ mod tests {
             let mut test_case = test_case;
 
             let date_from_str = |x| {
-                DateTime::<Utc>::from_utc(
-                    NaiveDate::parse_from_str(x, "%m/%d/%Y")
+                Utc.from_utc_datetime(
+                    &NaiveDate::parse_from_str(x, "%m/%d/%Y")
                         .unwrap()
                         .and_hms_opt(12, 0, 0)
                         .unwrap(),
-                    Utc,
                 )
             };
 
             let (_, values, dates): (f64, Vec<f64>, Vec<DateTime<Utc>>) = (
-                test_case
-                    .next()
-                    .unwrap()
-                    .parse::<f64>()
-                    .unwrap_or_else(|_| f64::NAN),
+                test_case.next().unwrap().parse::<f64>().unwrap_or(f64::NAN),
                 test_case
                     .by_ref()
                     .take_while(|x| x.parse::<f64>().is_ok())
                     .map(|x| x.parse::<f64>().unwrap())
                     .collect(),
-                test_case.map(|x| date_from_str(x)).collect(),
+                test_case.map(date_from_str).collect(),
             );
 
-            let res = financial::xirr(&values, &dates, None).unwrap_or_else(|_| f64::NAN);
+            let res = financial::xirr(&values, &dates, None).unwrap_or(f64::NAN);
 
             if !res.is_nan() {
                 assert_nearly_eq(
@@ -171,6 +303,41 @@ mod tests {
         });
     }
 
+    #[test]
+    fn yearfrac() {
+        test_fn("./tests/test_data/yearfrac.csv", |test_case, case_index| {
+            let mut test_case = test_case;
+
+            let date_from_str = |x: &str| NaiveDate::parse_from_str(x, "%m/%d/%Y").unwrap();
+
+            let (ans, start_date, end_date, basis): (f64, NaiveDate, NaiveDate, i32) = (
+                test_case.next().unwrap().parse::<f64>().unwrap(),
+                date_from_str(test_case.next().unwrap()),
+                date_from_str(test_case.next().unwrap()),
+                test_case.next().unwrap().parse::<i32>().unwrap(),
+            );
+
+            let basis = financial::Basis::try_from(basis).unwrap();
+            let res = financial::naive_date::yearfrac(start_date, end_date, Some(basis));
+            assert_nearly_eq(ans, res, case_index);
+        });
+    }
+
+    // the answer is written the way the spreadsheet displays it, so it only holds to its last decimal
+    fn assert_displayed_eq(ans: &str, res: f64, case_index: i32) {
+        let decimals = ans.split('.').nth(1).map_or(0, |decimals| decimals.len());
+        let tolerance = (0.5 * 10_f64.powi(-(decimals as i32))).max(PRECISION);
+        let ans = ans.parse::<f64>().unwrap();
+        assert!(
+            (ans - res).abs() <= tolerance,
+            "case {}: answer is {}, result is {}, diff is {}",
+            case_index,
+            ans,
+            res,
+            (ans - res).abs()
+        );
+    }
+
     fn assert_nearly_eq(ans: f64, res: f64, case_index: i32) {
         assert!(
             (ans - res).abs() < PRECISION,
@@ -193,12 +360,10 @@ mod tests {
     {
         if let Ok(lines) = read_lines(filename) {
             let mut case_index = 0;
-            for line in lines.skip(1) {
-                if let Ok(test_case) = line {
-                    let test_case = test_case.split(",");
-                    case_index += 1;
-                    func(test_case, case_index);
-                }
+            for test_case in lines.skip(1).flatten() {
+                let test_case = test_case.split(",");
+                case_index += 1;
+                func(test_case, case_index);
             }
         }
     }